@@ -47,7 +47,7 @@ pub fn benchmark(c: &mut Criterion) {
                 fl.remove(8);
                 fl
             }, 
-            |fl| { black_box({ fl.compactify(); }) }, 
+            |fl| { fl.compactify(); black_box(fl.size()) }, 
             BatchSize::SmallInput
         );
     });
@@ -60,7 +60,7 @@ pub fn benchmark(c: &mut Criterion) {
     c.bench_function("iter", |b| {
         b.iter_batched_ref(
             || fl.iter(),
-            |fl| black_box(for _v in fl { }),
+            |fl| for v in fl { black_box(v); },
             BatchSize::SmallInput
         );
     });
@@ -69,7 +69,7 @@ pub fn benchmark(c: &mut Criterion) {
     c.bench_function("into_iter", |b| {
         b.iter_batched_ref(
             || fl.clone().into_iter(),
-            |fl| black_box(for _v in fl { }),
+            |fl| for v in fl { black_box(v); },
             BatchSize::SmallInput
         );
     });
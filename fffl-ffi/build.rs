@@ -0,0 +1,22 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    generate_header();
+}
+
+/// Generates the C header for the crate within `OUT_DIR`.
+fn generate_header() {
+    use std::{env, path::PathBuf};
+
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=src/lib.rs");
+
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))
+        .expect("unable to read cbindgen.toml");
+
+    cbindgen::generate_with_config(&crate_dir, config)
+        .expect("unable to generate the C header")
+        .write_to_file(out_dir.join("fffl.h"));
+}
+
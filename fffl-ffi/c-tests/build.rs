@@ -0,0 +1,24 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    compile_c_tests();
+}
+
+/// Compiles the C tests against the committed headers of `fffl-ffi`, which checks that its
+/// header is up to date, and of `fffl`.
+fn compile_c_tests() {
+    use std::env;
+
+    println!("cargo:rerun-if-changed=src/tests.c");
+    println!("cargo:rerun-if-changed=../include/fffl.h");
+    println!("cargo:rerun-if-changed=../../include/fffl_slot.h");
+    println!("cargo:rerun-if-env-changed=CI");
+
+    cc::Build::new()
+        .file("src/tests.c")
+        .include("../include")
+        .include("../../include")
+        .std("c99")
+        // Warnings only fail the build in CI, so a newer compiler can't break local builds.
+        .warnings_into_errors(env::var_os("CI").is_some())
+        .compile("fffl_c_tests");
+}
@@ -0,0 +1,19 @@
+//! Runs the C tests in `src/tests.c` against the `fffl-ffi` bindings.
+//!
+//! Kept apart from `fffl-ffi` so that building the bindings never needs a C compiler.
+
+#[cfg(test)]
+mod tests {
+    // Links the bindings that the C tests call.
+    use fffl_ffi as _;
+
+    unsafe extern "C" {
+        /// Runs the tests in `src/tests.c`, returning the number of failed checks.
+        fn fffl_c_tests() -> std::ffi::c_int;
+    }
+
+    #[test]
+    fn c_tests() {
+        assert_eq!(unsafe { fffl_c_tests() }, 0);
+    }
+}
@@ -0,0 +1,74 @@
+/* C tests for the fffl-ffi crate, compiled by build.rs and run by the Rust tests of this crate. */
+#include <stddef.h>
+#include <stdio.h>
+
+#include "fffl.h"
+#include "fffl_slot.h"
+
+static int failures = 0;
+
+#define CHECK(cond) do { \
+    if (!(cond)) { fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #cond); failures++; } \
+} while (0)
+
+static void test_push_get_remove(void) {
+    int a = 1, b = 2, c = 3;
+    fffl_t *fl = fffl_new();
+
+    CHECK(fffl_push(fl, &a) == 0);
+    CHECK(fffl_push(fl, &b) == 1);
+
+    void *out = NULL;
+    CHECK(fffl_get(fl, 1, &out) && out == &b);
+    CHECK(!fffl_get(fl, 2, &out));
+
+    CHECK(fffl_remove(fl, 0, &out) && out == &a);
+    CHECK(!fffl_remove(fl, 0, NULL));
+    CHECK(!fffl_get(fl, 0, NULL));
+    CHECK(fffl_filled(fl) == 1 && fffl_size(fl) == 2);
+
+    /* Freed handles are reused first. */
+    CHECK(fffl_push(fl, &c) == 0);
+    CHECK(fffl_push(fl, &c) == 2);
+
+    fffl_free(fl);
+    fffl_free(NULL);
+}
+
+static void test_iter(void) {
+    int values[4] = {0, 1, 2, 3};
+    fffl_t *fl = fffl_with_capacity(4);
+    for (int i = 0; i < 4; i++) fffl_push(fl, &values[i]);
+    fffl_remove(fl, 1, NULL);
+
+    fffl_iter_t iter = fffl_iter_begin();
+    size_t index, count = 0, sum = 0;
+    void *value;
+    while (fffl_iter_next(fl, &iter, &index, &value)) {
+        CHECK(value == &values[index]);
+        sum += index;
+        count++;
+    }
+    CHECK(count == 3 && sum == 5);
+    CHECK(!fffl_iter_next(fl, &iter, NULL, NULL));
+
+    fffl_free(fl);
+}
+
+FFFL_SLOT(fffl_slot_u32, uint32_t);
+FFFL_SLOT(fffl_slot_u64, uint64_t);
+
+/* The sizes and offsets checked by the `repr_c_layout` test of fffl's raw module. */
+static void test_slot_layout(void) {
+    CHECK(sizeof(fffl_slot_u32) == sizeof(size_t) * 2);
+    CHECK(sizeof(fffl_slot_u64) == 16);
+    CHECK(offsetof(fffl_slot_u64, data) == 8);
+    CHECK(offsetof(fffl_slot_u32, data.value) == offsetof(fffl_slot_u32, data.next));
+}
+
+int fffl_c_tests(void) {
+    test_push_get_remove();
+    test_iter();
+    test_slot_layout();
+    return failures;
+}
@@ -0,0 +1,120 @@
+#ifndef FFFL_H
+#define FFFL_H
+
+/* Generated by cbindgen from src/lib.rs, do not edit by hand. */
+
+#include <stdbool.h>
+#include <stddef.h>
+
+/**
+ * An opaque freelist of `void *` values.
+ */
+typedef struct fffl_t fffl_t;
+
+/**
+ * A position within a freelist, used to iterate over its values.
+ *
+ * Iterating only reads the freelist when [`fffl_iter_next`] is called, so values may be
+ * pushed or removed between calls.  Values pushed behind the iterator are skipped.
+ */
+typedef struct {
+  /**
+   * The index at which to search for the next value.
+   */
+  size_t index;
+} fffl_iter_t;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Creates a new, empty freelist.  Must be released with [`fffl_free`].
+ */
+fffl_t *fffl_new(void);
+
+/**
+ * Creates a new, empty freelist with at least the specified capacity.
+ * Must be released with [`fffl_free`].
+ */
+fffl_t *fffl_with_capacity(size_t capacity);
+
+/**
+ * Releases a freelist.  Does nothing if `fl` is `NULL`.
+ *
+ * # Safety
+ *
+ * `fl` must be `NULL` or a freelist returned by [`fffl_new`] or [`fffl_with_capacity`]
+ * that has not yet been released.
+ */
+void fffl_free(fffl_t *fl);
+
+/**
+ * Pushes `value` to the first free slot (or back of the freelist), returning its index.
+ *
+ * # Safety
+ *
+ * `fl` must be a valid freelist.
+ */
+size_t fffl_push(fffl_t *fl, void *value);
+
+/**
+ * Reads the value at `index` into `out`, returning `false` if `index` is out of bounds
+ * or a free slot.  `out` may be `NULL` to only check for a value.
+ *
+ * # Safety
+ *
+ * `fl` must be a valid freelist, and `out` must be `NULL` or valid for writes.
+ */
+bool fffl_get(const fffl_t *fl, size_t index, void **out);
+
+/**
+ * Removes the value at `index`, writing it to `out` and returning `true`, or returns
+ * `false` if `index` is out of bounds or already a free slot.  `out` may be `NULL`.
+ *
+ * # Safety
+ *
+ * `fl` must be a valid freelist, and `out` must be `NULL` or valid for writes.
+ */
+bool fffl_remove(fffl_t *fl, size_t index, void **out);
+
+/**
+ * Returns the number of values held by the freelist.
+ *
+ * # Safety
+ *
+ * `fl` must be a valid freelist.
+ */
+size_t fffl_filled(const fffl_t *fl);
+
+/**
+ * Returns the number of slots in the freelist, including free slots.
+ *
+ * # Safety
+ *
+ * `fl` must be a valid freelist.
+ */
+size_t fffl_size(const fffl_t *fl);
+
+/**
+ * Returns an iterator positioned before the first value of any freelist.
+ */
+fffl_iter_t fffl_iter_begin(void);
+
+/**
+ * Advances `iter` to the next value of the freelist, writing its index and value to
+ * `index` and `value`, or returns `false` once there are no more values.  Either
+ * output may be `NULL`.
+ *
+ * # Safety
+ *
+ * `fl` must be a valid freelist, `iter` must be valid for reads and writes, and
+ * `index` and `value` must each be `NULL` or valid for writes.
+ */
+bool fffl_iter_next(const fffl_t *fl, fffl_iter_t *iter, size_t *index, void **value);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* FFFL_H */
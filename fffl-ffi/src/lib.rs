@@ -0,0 +1,231 @@
+//! A C ABI over a [`fffl::Freelist<void *>`](fffl::Freelist), for use as a handle table
+//! from C and C++.
+//!
+//! The functions below are exported from the crate's `cdylib` and `staticlib` targets,
+//! and the build script generates the matching C header `fffl.h` within its `OUT_DIR`.
+//! A copy of the header is kept at `include/fffl.h`.
+//!
+//! ```c
+//! #include "fffl.h"
+//!
+//! fffl_t *handles = fffl_new();
+//! size_t handle = fffl_push(handles, &player);
+//!
+//! void *value;
+//! if (fffl_get(handles, handle, &value)) { /* ... */ }
+//!
+//! fffl_iter_t iter = fffl_iter_begin();
+//! size_t index;
+//! while (fffl_iter_next(handles, &iter, &index, &value)) { /* ... */ }
+//!
+//! fffl_remove(handles, handle, NULL);
+//! fffl_free(handles);
+//! ```
+//!
+//! The freelist never dereferences the pointers it stores, so their lifetimes are up to
+//! the caller.  Any panic, e.g. when pushing exceeds the maximum capacity, aborts.
+
+use std::ffi::c_void;
+
+use fffl::Freelist;
+
+/// An opaque freelist of `void *` values.
+pub struct FfflFreelist(Freelist<*mut c_void>);
+
+/// A position within a freelist, used to iterate over its values.
+///
+/// Iterating only reads the freelist when [`fffl_iter_next`] is called, so values may be
+/// pushed or removed between calls.  Values pushed behind the iterator are skipped.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct FfflIter {
+    /// The index at which to search for the next value.
+    pub index: usize,
+}
+
+/// Creates a new, empty freelist.  Must be released with [`fffl_free`].
+#[unsafe(no_mangle)]
+pub extern "C" fn fffl_new() -> *mut FfflFreelist {
+    Box::into_raw(Box::new(FfflFreelist(Freelist::new())))
+}
+
+/// Creates a new, empty freelist with at least the specified capacity.
+/// Must be released with [`fffl_free`].
+#[unsafe(no_mangle)]
+pub extern "C" fn fffl_with_capacity(capacity: usize) -> *mut FfflFreelist {
+    Box::into_raw(Box::new(FfflFreelist(Freelist::with_capacity(capacity))))
+}
+
+/// Releases a freelist.  Does nothing if `fl` is `NULL`.
+///
+/// # Safety
+///
+/// `fl` must be `NULL` or a freelist returned by [`fffl_new`] or [`fffl_with_capacity`]
+/// that has not yet been released.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fffl_free(fl: *mut FfflFreelist) {
+    if !fl.is_null() { drop(unsafe { Box::from_raw(fl) }) }
+}
+
+/// Pushes `value` to the first free slot (or back of the freelist), returning its index.
+///
+/// # Safety
+///
+/// `fl` must be a valid freelist.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fffl_push(fl: *mut FfflFreelist, value: *mut c_void) -> usize {
+    unsafe { &mut *fl }.0.push(value)
+}
+
+/// Reads the value at `index` into `out`, returning `false` if `index` is out of bounds
+/// or a free slot.  `out` may be `NULL` to only check for a value.
+///
+/// # Safety
+///
+/// `fl` must be a valid freelist, and `out` must be `NULL` or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fffl_get(fl: *const FfflFreelist, index: usize, out: *mut *mut c_void) -> bool {
+    let fl = &unsafe { &*fl }.0;
+    let found = if index < fl.size() { fl.get(index).copied() } else { None };
+    found.map(|value| unsafe { write(out, value) }).is_some()
+}
+
+/// Removes the value at `index`, writing it to `out` and returning `true`, or returns
+/// `false` if `index` is out of bounds or already a free slot.  `out` may be `NULL`.
+///
+/// # Safety
+///
+/// `fl` must be a valid freelist, and `out` must be `NULL` or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fffl_remove(fl: *mut FfflFreelist, index: usize, out: *mut *mut c_void) -> bool {
+    let fl = &mut unsafe { &mut *fl }.0;
+    let removed = if index < fl.size() { fl.remove(index) } else { None };
+    removed.map(|value| unsafe { write(out, value) }).is_some()
+}
+
+/// Returns the number of values held by the freelist.
+///
+/// # Safety
+///
+/// `fl` must be a valid freelist.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fffl_filled(fl: *const FfflFreelist) -> usize {
+    unsafe { &*fl }.0.filled()
+}
+
+/// Returns the number of slots in the freelist, including free slots.
+///
+/// # Safety
+///
+/// `fl` must be a valid freelist.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fffl_size(fl: *const FfflFreelist) -> usize {
+    unsafe { &*fl }.0.size()
+}
+
+/// Returns an iterator positioned before the first value of any freelist.
+#[unsafe(no_mangle)]
+pub extern "C" fn fffl_iter_begin() -> FfflIter {
+    FfflIter { index: 0 }
+}
+
+/// Advances `iter` to the next value of the freelist, writing its index and value to
+/// `index` and `value`, or returns `false` once there are no more values.  Either
+/// output may be `NULL`.
+///
+/// # Safety
+///
+/// `fl` must be a valid freelist, `iter` must be valid for reads and writes, and
+/// `index` and `value` must each be `NULL` or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fffl_iter_next(
+    fl: *const FfflFreelist,
+    iter: *mut FfflIter,
+    index: *mut usize,
+    value: *mut *mut c_void
+) -> bool {
+    let fl = &unsafe { &*fl }.0;
+    let iter = unsafe { &mut *iter };
+    let Some((found, found_value)) = (iter.index..fl.size())
+        .find_map(|i| fl.get(i).map(|value| (i, *value)))
+    else {
+        iter.index = fl.size();
+        return false
+    };
+
+    iter.index = found + 1;
+    unsafe {
+        write(index, found);
+        write(value, found_value);
+    }
+    true
+}
+
+/// Writes `value` to `out` unless it's `NULL`.
+#[inline]
+unsafe fn write<T>(out: *mut T, value: T) {
+    if !out.is_null() { unsafe { out.write(value) } }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::ptr::{null_mut, without_provenance_mut};
+
+    use super::*;
+
+    fn handle(value: usize) -> *mut c_void { without_provenance_mut(value) }
+
+    #[test]
+    fn push_get_remove() {
+        unsafe {
+            let fl = fffl_new();
+            assert_eq!(fffl_push(fl, handle(10)), 0);
+            assert_eq!(fffl_push(fl, handle(11)), 1);
+
+            let mut out = null_mut();
+            assert!(fffl_get(fl, 1, &mut out));
+            assert_eq!(out, handle(11));
+            assert!(!fffl_get(fl, 2, &mut out));
+
+            assert!(fffl_remove(fl, 0, &mut out));
+            assert_eq!(out, handle(10));
+            assert!(!fffl_remove(fl, 0, null_mut()));
+            assert!(!fffl_get(fl, 0, null_mut()));
+            assert_eq!((fffl_filled(fl), fffl_size(fl)), (1, 2));
+
+            assert_eq!(fffl_push(fl, handle(12)), 0);
+            fffl_free(fl);
+            fffl_free(null_mut());
+        }
+    }
+
+    #[test]
+    fn iter() {
+        unsafe {
+            let fl = fffl_with_capacity(4);
+            for value in 0..4 { fffl_push(fl, handle(value)); }
+            fffl_remove(fl, 1, null_mut());
+
+            let mut iter = fffl_iter_begin();
+            let (mut index, mut value) = (0, null_mut());
+            let mut seen = Vec::new();
+            while fffl_iter_next(fl, &mut iter, &mut index, &mut value) {
+                seen.push((index, value));
+                // Removing the current value while iterating is allowed.
+                if index == 2 { fffl_remove(fl, 2, null_mut()); }
+            }
+
+            assert_eq!(seen, [(0, handle(0)), (2, handle(2)), (3, handle(3))]);
+            assert!(!fffl_iter_next(fl, &mut iter, null_mut(), null_mut()));
+            fffl_free(fl);
+        }
+    }
+
+    #[test]
+    fn header_is_up_to_date() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/fffl.h"));
+        let committed = include_str!("../include/fffl.h");
+        assert!(generated == committed, "include/fffl.h is out of date, copy it from {}", env!("OUT_DIR"));
+    }
+}
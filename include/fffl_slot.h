@@ -0,0 +1,26 @@
+#ifndef FFFL_SLOT_H
+#define FFFL_SLOT_H
+
+/* Layout of the slots of a fffl Freelist built with the `repr-c` feature, see `fffl::raw`. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+/* Values of a slot's `tag`. */
+enum {
+    FFFL_SLOT_VALUE = 0, /* `data.value` holds a value. */
+    FFFL_SLOT_NEXT = 1,  /* `data.next` is the index of the next free slot. */
+    FFFL_SLOT_EMPTY = 2  /* A free slot ending the chain of free slots. */
+};
+
+/* Declares `name` as the slot type of a `Freelist<T>`, for a `T` with a C layout. */
+#define FFFL_SLOT(name, T) \
+    typedef struct name { \
+        uint8_t tag; \
+        union { \
+            T value; \
+            size_t next; \
+        } data; \
+    } name
+
+#endif /* FFFL_SLOT_H */
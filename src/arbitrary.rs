@@ -0,0 +1,53 @@
+//! Generation of arbitrary freelists with [`arbitrary`](https://docs.rs/arbitrary), for fuzzing.
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{Freelist, fragmented};
+
+impl<'a, T: Arbitrary<'a>> Arbitrary<'a> for Freelist<T> {
+    /// Generates a freelist with arbitrary values, free slots and order of free slots.
+    ///
+    /// The freelist is built by pushing every value and then removing some of them in an
+    /// arbitrary order, so it always upholds the freelist's invariants.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(fragmented::<T, u32>(u.arbitrary()?))
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        Ok(fragmented::<T, u32>(Arbitrary::arbitrary_take_rest(u)?))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arbitrary() {
+        let mut seed = 0x2545_f491_u32;
+        let bytes = (0..4096)
+            .map(|_| { seed ^= seed << 13; seed ^= seed >> 17; seed ^= seed << 5; seed as u8 })
+            .collect::<Vec<u8>>();
+        let mut u = Unstructured::new(&bytes);
+
+        let mut fragmented_count = 0;
+        while !u.is_empty() {
+            let mut fl = Freelist::<u16>::arbitrary(&mut u).unwrap();
+            assert_eq!(fl.iter().count(), fl.filled());
+            assert_eq!(fl.vacant_indices().count(), fl.free());
+            if fl.free() > 0 { fragmented_count += 1; }
+
+            let free = fl.vacant_indices().collect::<Vec<_>>();
+            for index in free { assert_eq!(fl.push(0), index); }
+            assert_eq!(fl.free(), 0);
+        }
+        assert!(fragmented_count > 0);
+    }
+
+    #[test]
+    fn arbitrary_take_rest() {
+        let fl = Freelist::<u8>::arbitrary_take_rest(Unstructured::new(&[1, 2, 1, 0, 0, 3, 1, 0, 0, 0, 0])).unwrap();
+        assert_eq!(fl.size(), fl.filled() + fl.free());
+    }
+}
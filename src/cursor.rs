@@ -0,0 +1,214 @@
+use crate::{Freelist, Slot};
+
+/// Where a value pushed through a [`CursorMut`] landed relative to the cursor's position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Placement {
+    /// The value landed at an index ahead of the cursor and will be reached by
+    /// [`move_next`](CursorMut::move_next).
+    Ahead(usize),
+    /// The value landed at an index behind the cursor and will be reached by
+    /// [`move_prev`](CursorMut::move_prev).
+    Behind(usize),
+}
+
+impl Placement {
+    /// Returns the index the value was pushed to.
+    #[inline]
+    pub const fn index(self) -> usize {
+        match self {
+            Placement::Ahead(index) | Placement::Behind(index) => index
+        }
+    }
+}
+
+/// A cursor over a [`Freelist`] with editing operations.
+///
+/// The cursor points at a filled slot, or at a "ghost" position that sits both before
+/// the first value and after the last.  Moving past either end of the freelist moves the
+/// cursor onto the ghost, and moving again from the ghost wraps around to the opposite end.
+///
+/// Created by [`Freelist::cursor_mut`].
+#[derive(Debug)]
+pub struct CursorMut<'a, T: 'a> {
+    freelist: &'a mut Freelist<T>,
+    index: Option<usize>,
+}
+
+impl<'a, T: 'a> CursorMut<'a, T> {
+
+    #[inline]
+    pub(crate) fn new(freelist: &'a mut Freelist<T>) -> Self {
+        let index = freelist.slots.iter().position(Slot::is_value);
+        Self { freelist, index }
+    }
+
+    /// Returns the index the cursor is pointing at, or `None` if it's on the ghost position.
+    #[inline]
+    pub fn index(&self) -> Option<usize> { self.index }
+
+    /// Moves the cursor to the next filled slot.
+    ///
+    /// If the cursor is on the ghost position it moves to the first value, and if it's
+    /// on the last value it moves to the ghost position.
+    pub fn move_next(&mut self) {
+        let start = self.index.map_or(0, |index| index + 1);
+        self.index = self.freelist.slots[start..].iter()
+            .position(Slot::is_value)
+            .map(|offset| start + offset);
+    }
+
+    /// Moves the cursor to the previous filled slot.
+    ///
+    /// If the cursor is on the ghost position it moves to the last value, and if it's
+    /// on the first value it moves to the ghost position.
+    pub fn move_prev(&mut self) {
+        let end = self.index.unwrap_or(self.freelist.slots.len());
+        self.index = self.freelist.slots[..end].iter().rposition(Slot::is_value);
+    }
+
+    /// Returns the index and a mutable reference to the value the cursor is pointing at,
+    /// or `None` if it's on the ghost position.
+    #[inline]
+    pub fn current(&mut self) -> Option<(usize, &mut T)> {
+        let index = self.index?;
+        Some((index, unsafe { self.freelist.get_unchecked_mut(index) }))
+    }
+
+    /// Removes the value the cursor is pointing at and moves the cursor to the next
+    /// filled slot, returning the removed value.
+    ///
+    /// If the cursor is on the ghost position, nothing is removed and `None` is returned.
+    /// The freed slot is reused exactly as if [`Freelist::remove`] had been called.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let index = self.index?;
+        let value = unsafe { self.freelist.remove_unchecked(index) };
+        self.move_next();
+        Some(value)
+    }
+
+    /// Pushes a value onto the freelist (see [`Freelist::push`]) without moving the cursor,
+    /// reporting where the value landed relative to the cursor.
+    ///
+    /// While on the ghost position, all values are considered ahead of the cursor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fffl::{Freelist, Placement};
+    ///
+    /// let mut fl = Freelist::from([1, 2, 3]);
+    /// let _ = fl.remove(0); // Some(1)
+    ///
+    /// let mut cursor = fl.cursor_mut();
+    /// assert_eq!(cursor.index(), Some(1));
+    ///
+    /// assert_eq!(cursor.push(4), Placement::Behind(0));
+    /// assert_eq!(cursor.push(5), Placement::Ahead(3));
+    /// ```
+    pub fn push(&mut self, value: T) -> Placement {
+        let index = self.freelist.push(value);
+        match self.index {
+            Some(current) if index < current => Placement::Behind(index),
+            _ => Placement::Ahead(index)
+        }
+    }
+
+    /// Returns a reference to the underlying freelist.
+    #[inline]
+    pub fn as_freelist(&self) -> &Freelist<T> { self.freelist }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new() {
+        let mut fl = Freelist::from([0, 1, 2]);
+        fl.remove(0);
+        assert_eq!(CursorMut::new(&mut fl).index(), Some(1));
+
+        let mut fl = Freelist::<i32>::new();
+        assert_eq!(CursorMut::new(&mut fl).index(), None);
+    }
+
+    #[test]
+    fn move_next() {
+        let mut fl = Freelist::from([0, 1, 2, 3]);
+        fl.remove(2);
+        let mut cursor = CursorMut::new(&mut fl);
+
+        for index in [Some(1), Some(3), None, Some(0)] {
+            cursor.move_next();
+            assert_eq!(cursor.index(), index);
+        }
+    }
+
+    #[test]
+    fn move_prev() {
+        let mut fl = Freelist::from([0, 1, 2, 3]);
+        fl.remove(1);
+        let mut cursor = CursorMut::new(&mut fl);
+
+        for index in [None, Some(3), Some(2), Some(0)] {
+            cursor.move_prev();
+            assert_eq!(cursor.index(), index);
+        }
+    }
+
+    #[test]
+    fn current() {
+        let mut fl = Freelist::from([0, 1]);
+        let mut cursor = CursorMut::new(&mut fl);
+
+        *cursor.current().unwrap().1 = 10;
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some((1, &mut 1)));
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(fl.to_vec(), [10, 1]);
+    }
+
+    #[test]
+    fn remove_current() {
+        let mut fl = Freelist::from([0, 1, 2, 3]);
+        fl.remove(2);
+        let mut cursor = CursorMut::new(&mut fl);
+        cursor.move_next();
+
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!(cursor.index(), Some(3));
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+
+        assert_eq!(fl.filled(), 1);
+        assert_eq!(fl.next_available(), 3);
+    }
+
+    #[test]
+    fn push() {
+        let mut fl = Freelist::from([0, 1, 2, 3]);
+        fl.remove(3);
+        fl.remove(0);
+        let mut cursor = CursorMut::new(&mut fl);
+        cursor.move_next();
+
+        assert_eq!(cursor.push(4), Placement::Behind(0));
+        assert_eq!(cursor.push(5), Placement::Ahead(3));
+        assert_eq!(cursor.push(6), Placement::Ahead(4));
+        assert_eq!(cursor.index(), Some(2));
+
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.push(7), Placement::Ahead(5));
+    }
+
+    #[test]
+    fn placement_index() {
+        assert_eq!(Placement::Ahead(3).index(), 3);
+        assert_eq!(Placement::Behind(1).index(), 1);
+    }
+}
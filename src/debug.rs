@@ -0,0 +1,72 @@
+use std::fmt;
+
+use crate::Freelist;
+
+impl<T: fmt::Debug> fmt::Debug for Freelist<T> {
+    /// Formats the freelist as a map from each filled index to its value, e.g. `{0: 'a', 2: 'c'}`.
+    ///
+    /// The alternate form (`{:#?}`) additionally summarises the free slots.
+    /// See [`debug_layout`](Freelist::debug_layout) for the freelist's internal layout.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.alternate() {
+            false => Values(self).fmt(f),
+            true => f.debug_struct("Freelist")
+                .field("values", &Values(self))
+                .field("free", &self.free())
+                .field("next_available", &self.next_available())
+                .finish()
+        }
+    }
+}
+
+struct Values<'a, T>(&'a Freelist<T>);
+
+impl<T: fmt::Debug> fmt::Debug for Values<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.0.iter_indexed()).finish()
+    }
+}
+
+/// Formats the internal layout of a [`Freelist`], including its chain of free slots.
+///
+/// Created by [`Freelist::debug_layout`].
+pub struct DebugLayout<'a, T>(pub(crate) &'a Freelist<T>);
+
+impl<T: fmt::Debug> fmt::Debug for DebugLayout<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Freelist")
+            .field("slots", &self.0.slots)
+            .field("next", &self.0.next)
+            .field("filled_length", &self.0.filled_length)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::fragmented;
+
+    #[test]
+    fn debug() {
+        assert_eq!(format!("{:?}", fragmented()), r#"{1: "b", 2: "c"}"#);
+        assert_eq!(format!("{:?}", Freelist::<char>::new()), "{}");
+    }
+
+    #[test]
+    fn debug_alternate() {
+        assert_eq!(
+            format!("{:#?}", fragmented()),
+            "Freelist {\n    values: {\n        1: \"b\",\n        2: \"c\",\n    },\n    free: 3,\n    next_available: 4,\n}"
+        );
+    }
+
+    #[test]
+    fn debug_layout() {
+        assert_eq!(
+            format!("{:?}", fragmented().debug_layout()),
+            r#"Freelist { slots: [Next(3), Value("b"), Value("c"), Empty, Next(0)], next: Next(4), filled_length: 2 }"#
+        );
+    }
+}
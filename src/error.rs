@@ -0,0 +1,95 @@
+use std::{error::Error, fmt};
+
+/// The error type returned by [`Freelist::get_disjoint_mut`](crate::Freelist::get_disjoint_mut).
+///
+/// It indicates one of three possible errors:
+/// - An index is out-of-bounds.
+/// - An index refers to a free slot.
+/// - The same index appeared multiple times.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GetDisjointError {
+    /// An index provided was out-of-bounds for the freelist.
+    IndexOutOfBounds,
+    /// An index provided refers to a free slot.
+    VacantSlot,
+    /// Two indices provided were overlapping.
+    OverlappingIndices,
+}
+
+impl fmt::Display for GetDisjointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            GetDisjointError::IndexOutOfBounds => "an index is out of bounds",
+            GetDisjointError::VacantSlot => "an index refers to a free slot",
+            GetDisjointError::OverlappingIndices => "there were overlapping indices",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Error for GetDisjointError {}
+
+/// The error type returned by [`Freelist::from_sparse`](crate::Freelist::from_sparse)
+/// when the same index appears more than once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateIndexError {
+    pub(crate) index: usize,
+}
+
+impl DuplicateIndexError {
+    /// Returns the index that appeared more than once.
+    #[inline]
+    pub fn index(&self) -> usize { self.index }
+}
+
+impl fmt::Display for DuplicateIndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "index {} appeared more than once", self.index)
+    }
+}
+
+impl Error for DuplicateIndexError {}
+
+/// The error type returned by [`Freelist::validate`](crate::Freelist::validate),
+/// describing the first broken invariant found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// The recorded number of filled slots differs from the number of slots holding a value.
+    FilledMismatch {
+        /// The number of filled slots recorded by the freelist.
+        recorded: usize,
+        /// The number of slots actually holding a value.
+        actual: usize,
+    },
+    /// The head of the chain of free slots holds a value.
+    ValueAtHead,
+    /// The chain of free slots links to an index that is out-of-bounds.
+    LinkOutOfBounds(usize),
+    /// The chain of free slots links to a slot holding a value.
+    LinkToValue(usize),
+    /// The chain of free slots visits the same slot more than once.
+    Cycle(usize),
+    /// A free slot is not part of the chain of free slots, e.g. a stray terminator.
+    Unlinked(usize),
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvariantViolation::FilledMismatch { recorded, actual } =>
+                write!(f, "the freelist records {recorded} filled slots but holds {actual} values"),
+            InvariantViolation::ValueAtHead =>
+                f.write_str("the head of the free chain holds a value"),
+            InvariantViolation::LinkOutOfBounds(index) =>
+                write!(f, "the free chain links to index {index}, which is out of range"),
+            InvariantViolation::LinkToValue(index) =>
+                write!(f, "the free chain links to index {index}, which is a filled slot"),
+            InvariantViolation::Cycle(index) =>
+                write!(f, "the free chain is cyclic, revisiting index {index}"),
+            InvariantViolation::Unlinked(index) =>
+                write!(f, "the free chain does not visit every free slot, missing index {index}"),
+        }
+    }
+}
+
+impl Error for InvariantViolation {}
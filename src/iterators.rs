@@ -1,16 +1,23 @@
-
 mod iter;
 mod iter_mut;
 mod into_iter;
+mod iter_indexed;
+mod iter_mut_indexed;
+mod into_iter_indexed;
+mod keys;
+mod drain;
+mod extract_if;
+mod vacant_indices;
+mod free_runs;
 
 pub use into_iter::IntoIterFl;
 pub use iter_mut::IterMutFl;
 pub use iter::IterFl;
-
-use crate::Slot;
-
-#[inline(always)]
-pub(super) const fn size_hint<T>(start: usize, end: usize) -> (usize, Option<usize>) {
-    let len = (end - start) / std::mem::size_of::<Slot<T>>();
-    (len, Some(len))
-}
\ No newline at end of file
+pub use into_iter_indexed::IntoIterIndexedFl;
+pub use iter_mut_indexed::IterMutIndexedFl;
+pub use iter_indexed::IterIndexedFl;
+pub use keys::KeysFl;
+pub use drain::DrainFl;
+pub use extract_if::ExtractIfFl;
+pub use vacant_indices::VacantIndicesFl;
+pub use free_runs::FreeRunsFl;
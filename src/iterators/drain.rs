@@ -0,0 +1,177 @@
+use std::{iter::{ExactSizeIterator, FusedIterator}, marker::PhantomData, ptr};
+
+use crate::{Freelist, Slot};
+
+pub struct DrainFl<'a, T: 'a> {
+    start: *mut Slot<T>,
+    end: *mut Slot<T>,
+    origin: *mut Slot<T>,
+    remaining: usize,
+    _marker: PhantomData<&'a mut Freelist<T>>
+}
+
+impl<'a, T: 'a> DrainFl<'a, T> {
+
+    #[inline]
+    pub(crate) fn new(freelist: &'a mut Freelist<T>) -> Self {
+        let len = freelist.slots.len();
+        let remaining = freelist.filled_length;
+
+        // Empty the freelist up front so that leaking the iterator (e.g. with
+        // `mem::forget`) can only ever leak values, never expose moved-out slots.
+        unsafe { freelist.slots.set_len(0) }
+        freelist.next = Slot::Empty;
+        freelist.filled_length = 0;
+        freelist.debug_validate();
+
+        let start = freelist.slots.as_mut_ptr();
+        Self {
+            start,
+            end: unsafe { start.add(len) },
+            origin: start,
+            remaining,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn read_slot(&self, slot: *mut Slot<T>) -> (usize, T) {
+        unsafe { (slot.offset_from(self.origin) as usize, slot.read().into_value_unchecked()) }
+    }
+}
+
+impl<'a, T: 'a> Iterator for DrainFl<'a, T> {
+    type Item = (usize, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.start < self.end {
+            let curr = self.start;
+            unsafe {
+                self.start = curr.add(1);
+                if (*curr).is_value() {
+                    self.remaining -= 1;
+                    return Some(self.read_slot(curr))
+                }
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T: 'a> DoubleEndedIterator for DrainFl<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.start < self.end {
+            unsafe {
+                self.end = self.end.offset(-1);
+                if (*self.end).is_value() {
+                    self.remaining -= 1;
+                    return Some(self.read_slot(self.end))
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for DrainFl<'a, T> {}
+
+impl<'a, T: 'a> FusedIterator for DrainFl<'a, T> {}
+
+impl<'a, T: 'a> Drop for DrainFl<'a, T> {
+    fn drop(&mut self) {
+        // Drops every slot that has not been yielded.  Should one of the values
+        // panic while dropping, the remaining slots are still dropped during unwinding.
+        let unyielded = ptr::slice_from_raw_parts_mut(
+            self.start,
+            unsafe { self.end.offset_from(self.start) as usize }
+        );
+        self.start = self.end;
+        unsafe { ptr::drop_in_place(unyielded) }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, mem, panic::{self, AssertUnwindSafe}, rc::Rc};
+
+    use super::*;
+    use crate::test_util::{Bomb, counted};
+
+    #[test]
+    fn next() {
+        let mut fl = Freelist::from([0, 1, 2, 3]);
+        fl.remove(1);
+        let mut iter = DrainFl::new(&mut fl);
+
+        assert_eq!(iter.next(), Some((0, 0)));
+        assert_eq!(iter.next(), Some((2, 2)));
+        assert_eq!(iter.next(), Some((3, 3)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn next_back() {
+        let mut fl = Freelist::from([0, 1, 2, 3]);
+        fl.remove(2);
+        let mut iter = DrainFl::new(&mut fl);
+
+        assert_eq!(iter.next_back(), Some((3, 3)));
+        assert_eq!(iter.next_back(), Some((1, 1)));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next(), Some((0, 0)));
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn drops_unyielded() {
+        let rc = Rc::new(());
+        let mut fl = Freelist::from(vec![rc.clone(), rc.clone(), rc.clone(), rc.clone()]);
+        fl.remove(1);
+        assert_eq!(Rc::strong_count(&rc), 4);
+
+        let mut iter = DrainFl::new(&mut fl);
+        let (index, value) = iter.next().unwrap();
+        assert_eq!(index, 0);
+        assert_eq!(Rc::strong_count(&rc), 4);
+        drop(value);
+        drop(iter);
+
+        assert_eq!(Rc::strong_count(&rc), 1);
+        assert_eq!(fl.filled(), 0);
+        assert_eq!(fl.size(), 0);
+    }
+
+    #[test]
+    fn leak() {
+        let drops = Cell::new(0);
+        let mut fl = counted(&drops, 2);
+        let capacity = fl.capacity();
+
+        mem::forget(DrainFl::new(&mut fl));
+
+        assert_eq!(fl.filled(), 0);
+        assert_eq!(fl.size(), 0);
+        assert_eq!(fl.capacity(), capacity);
+        drop(fl);
+        assert_eq!(drops.get(), 0);
+    }
+
+    #[test]
+    fn panicking_drop() {
+        let rc = Rc::new(());
+        let mut fl = Freelist::from([false, true, false].map(|explode| Bomb::new(&rc, explode)));
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| drop(DrainFl::new(&mut fl))));
+
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+        assert_eq!(fl.filled(), 0);
+        assert_eq!(fl.size(), 0);
+    }
+}
@@ -0,0 +1,111 @@
+use std::iter::FusedIterator;
+
+use crate::{Freelist, Slot};
+
+pub struct ExtractIfFl<'a, T: 'a, F> {
+    freelist: &'a mut Freelist<T>,
+    index: usize,
+    pred: F
+}
+
+impl<'a, T: 'a, F> ExtractIfFl<'a, T, F>
+where
+    F: FnMut(usize, &mut T) -> bool
+{
+    #[inline]
+    pub(crate) fn new(freelist: &'a mut Freelist<T>, pred: F) -> Self {
+        Self { freelist, index: 0, pred }
+    }
+}
+
+impl<'a, T: 'a, F> Iterator for ExtractIfFl<'a, T, F>
+where
+    F: FnMut(usize, &mut T) -> bool
+{
+    type Item = (usize, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.freelist.slots.len() {
+            let index = self.index;
+            self.index += 1;
+            if let Slot::Value(value) = &mut self.freelist.slots[index] {
+                // The freelist is never left mid-removal, so a panicking
+                // predicate leaves it in a consistent state.
+                if (self.pred)(index, value) {
+                    return Some((index, unsafe { self.freelist.remove_unchecked(index) }))
+                }
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.freelist.filled_length))
+    }
+}
+
+impl<'a, T: 'a, F> FusedIterator for ExtractIfFl<'a, T, F>
+where
+    F: FnMut(usize, &mut T) -> bool
+{}
+
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{self, AssertUnwindSafe};
+
+    use super::*;
+
+    #[test]
+    fn next() {
+        let mut fl = Freelist::from([0, 1, 2, 3, 4, 5]);
+        fl.remove(2);
+        let mut iter = ExtractIfFl::new(&mut fl, |_, v| *v % 2 == 1);
+
+        assert_eq!(iter.next(), Some((1, 1)));
+        assert_eq!(iter.next(), Some((3, 3)));
+        assert_eq!(iter.next(), Some((5, 5)));
+        assert_eq!(iter.next(), None);
+
+        assert_eq!(fl.filled(), 2);
+        assert_eq!(fl.slots, [Slot::Value(0), Slot::Next(2), Slot::Empty, Slot::Next(1), Slot::Value(4), Slot::Next(3)]);
+        assert_eq!(fl.next, Slot::Next(5));
+    }
+
+    #[test]
+    fn lazy() {
+        let mut fl = Freelist::from([0, 1, 2, 3]);
+        assert_eq!(ExtractIfFl::new(&mut fl, |_, _| true).next(), Some((0, 0)));
+
+        assert_eq!(fl.filled(), 3);
+        assert_eq!(fl.keys().collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(fl.next_available(), 0);
+    }
+
+    #[test]
+    fn update_kept() {
+        let mut fl = Freelist::from([0, 1, 2, 3]);
+        let extracted = ExtractIfFl::new(&mut fl, |index, v| { *v *= 10; index == 2 })
+            .collect::<Vec<_>>();
+
+        assert_eq!(extracted, [(2, 20)]);
+        assert_eq!(fl.to_vec(), [0, 10, 30]);
+    }
+
+    #[test]
+    fn panicking_predicate() {
+        let mut fl = Freelist::from([0, 1, 2, 3]);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            ExtractIfFl::new(&mut fl, |index, _| if index == 2 { panic!("boom") } else { true })
+                .for_each(drop)
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(fl.filled(), 2);
+        assert_eq!(fl.keys().collect::<Vec<_>>(), [2, 3]);
+        assert_eq!(fl.push(4), 1);
+        assert_eq!(fl.push(5), 0);
+        assert_eq!(fl.push(6), 4);
+    }
+}
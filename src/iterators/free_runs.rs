@@ -0,0 +1,95 @@
+use std::{iter::FusedIterator, ops::Range};
+
+use crate::Slot;
+
+pub struct FreeRunsFl<'a, T: 'a> {
+    slots: &'a [Slot<T>],
+    front: usize,
+    back: usize
+}
+
+impl<'a, T: 'a> FreeRunsFl<'a, T> {
+
+    #[inline]
+    pub(crate) const fn new(slots: &'a [Slot<T>]) -> Self {
+        Self { slots, front: 0, back: slots.len() }
+    }
+}
+
+impl<'a, T: 'a> Iterator for FreeRunsFl<'a, T> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.front < self.back && self.slots[self.front].is_value() { self.front += 1 }
+        if self.front == self.back { return None }
+
+        let start = self.front;
+        while self.front < self.back && !self.slots[self.front].is_value() { self.front += 1 }
+        Some(start..self.front)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // At most every other slot may begin a run.
+        (0, Some((self.back - self.front).div_ceil(2)))
+    }
+}
+
+impl<'a, T: 'a> DoubleEndedIterator for FreeRunsFl<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.front < self.back && self.slots[self.back - 1].is_value() { self.back -= 1 }
+        if self.front == self.back { return None }
+
+        let end = self.back;
+        while self.front < self.back && !self.slots[self.back - 1].is_value() { self.back -= 1 }
+        Some(self.back..end)
+    }
+}
+
+impl<'a, T: 'a> FusedIterator for FreeRunsFl<'a, T> {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static SLICE: &[Slot<i32>] = &[
+        Slot::Next(1), Slot::Next(5), Slot::Value(2), Slot::Value(3),
+        Slot::Next(6), Slot::Next(4), Slot::Empty, Slot::Value(7)
+    ];
+
+    #[test]
+    fn next() {
+        let mut iter = FreeRunsFl::new(SLICE);
+
+        assert_eq!(iter.next(), Some(0..2));
+        assert_eq!(iter.next(), Some(4..7));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn next_back() {
+        let mut iter = FreeRunsFl::new(SLICE);
+
+        assert_eq!(iter.next_back(), Some(4..7));
+        assert_eq!(iter.next_back(), Some(0..2));
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn mixed() {
+        let slice = &[Slot::Empty, Slot::Next(0), Slot::Next(1), Slot::<i32>::Next(2)];
+        let mut iter = FreeRunsFl::new(slice);
+
+        assert_eq!(iter.next_back(), Some(0..4));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn no_runs() {
+        let mut iter = FreeRunsFl::new(&[Slot::Value(0), Slot::Value(1)]);
+
+        assert_eq!(iter.size_hint(), (0, Some(1)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+}
@@ -1,73 +1,126 @@
-
-use std::iter::FusedIterator;
+use std::{iter::{ExactSizeIterator, FusedIterator}, marker::PhantomData, mem::ManuallyDrop, ptr::{self, NonNull}};
 
 use crate::{Freelist, Slot};
 
-use super::size_hint;
-
 pub struct IntoIterFl<T> {
+    // `NonNull` rather than `*mut` keeps the iterator covariant in `T`, like the `Freelist`.
+    buf: NonNull<Slot<T>>,
+    cap: usize,
     start: *const Slot<T>,
     end: *const Slot<T>,
-    _fl: Freelist<T>
+    remaining: usize,
+    _marker: PhantomData<T>
 }
 
+unsafe impl<T: Send> Send for IntoIterFl<T> {}
+unsafe impl<T: Sync> Sync for IntoIterFl<T> {}
+
 impl<T> IntoIterFl<T> {
     #[inline]
     pub(crate) fn new(freelist: Freelist<T>) -> Self {
-        let start = freelist.slots.as_ptr();
+        // Take ownership of the buffer so that the slots' values are only ever
+        // dropped by this iterator, once each, rather than by the `Vec` as well.
+        let mut slots = ManuallyDrop::new(freelist.slots);
+        let buf = unsafe { NonNull::new_unchecked(slots.as_mut_ptr()) };
         Self {
-            start,
-            end: match freelist.slots.len() {
-                0 => start,
-                count @ _ => unsafe { start.add(count) }
-            },
-            _fl: freelist
+            buf,
+            cap: slots.capacity(),
+            start: buf.as_ptr(),
+            end: unsafe { buf.as_ptr().add(slots.len()) },
+            remaining: freelist.filled_length,
+            _marker: PhantomData
         }
     }
-}
 
-impl<T> Iterator for IntoIterFl<T> {
-    type Item = T;
-
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Advances the front of the iterator to the next slot containing a value.
+    #[inline]
+    pub(super) fn next_ptr(&mut self) -> Option<*const Slot<T>> {
         while self.start < self.end {
             let curr = self.start;
             unsafe {
                 self.start = curr.add(1);
-                if let Slot::Value(value) = curr.read() { return Some(value) }
+                if (*curr).is_value() {
+                    self.remaining -= 1;
+                    return Some(curr)
+                }
             }
         }
         None
     }
 
+    /// Advances the back of the iterator to the previous slot containing a value.
     #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        size_hint::<T>(self.start as usize, self.end as usize)
-    }
-}
-
-impl<T> DoubleEndedIterator for IntoIterFl<T> {
-    fn next_back(&mut self) -> Option<Self::Item> {
+    pub(super) fn next_back_ptr(&mut self) -> Option<*const Slot<T>> {
         while self.start < self.end {
             unsafe {
                 self.end = self.end.offset(-1);
-                if let Slot::Value(value) = self.end.read() { return Some(value) }
+                if (*self.end).is_value() {
+                    self.remaining -= 1;
+                    return Some(self.end)
+                }
             }
         }
         None
     }
+
+    /// Returns the freelist index of a slot yielded by this iterator.
+    #[inline(always)]
+    pub(super) fn index_of(&self, slot: *const Slot<T>) -> usize {
+        unsafe { slot.offset_from(self.buf.as_ptr()) as usize }
+    }
 }
 
+impl<T> Iterator for IntoIterFl<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_ptr().map(|slot| unsafe { slot.read().into_value_unchecked() })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIterFl<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next_back_ptr().map(|slot| unsafe { slot.read().into_value_unchecked() })
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIterFl<T> {}
+
 impl<T> FusedIterator for IntoIterFl<T> {}
 
 impl<T> Drop for IntoIterFl<T> {
-    fn drop(&mut self) { for _ in &mut * self { } }
+    fn drop(&mut self) {
+        // Frees the buffer once the unyielded slots are dropped, even if one of them panics.
+        struct DropBuffer<T> { buf: NonNull<Slot<T>>, cap: usize }
+
+        impl<T> Drop for DropBuffer<T> {
+            fn drop(&mut self) { unsafe { drop(Vec::from_raw_parts(self.buf.as_ptr(), 0, self.cap)) } }
+        }
+
+        let _buffer = DropBuffer { buf: self.buf, cap: self.cap };
+        let unyielded = ptr::slice_from_raw_parts_mut(
+            self.start as *mut Slot<T>,
+            unsafe { self.end.offset_from(self.start) as usize }
+        );
+        self.start = self.end;
+        unsafe { ptr::drop_in_place(unyielded) }
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
-        use super::*;
+    use std::{cell::Cell, panic::{self, AssertUnwindSafe}, rc::Rc};
+
+    use super::*;
+    use crate::test_util::{Bomb, counted};
 
     #[test]
     fn next() {
@@ -111,8 +164,65 @@ mod tests {
         fl.remove(0);
         fl.remove(2);
         let mut iter = IntoIterFl::new(fl);
-        assert_eq!(iter.size_hint(), (4, Some(4)));
-        iter.next();
         assert_eq!(iter.size_hint(), (2, Some(2)));
+        iter.next();
+        assert_eq!(iter.size_hint(), (1, Some(1)));
+    }
+
+    #[test]
+    fn drops_once() {
+        let drops = Cell::new(0);
+        let mut fl = counted(&drops, 4);
+        fl.remove(1);
+        assert_eq!(drops.get(), 1);
+
+        let yielded = IntoIterFl::new(fl).collect::<Vec<_>>();
+        assert_eq!(drops.get(), 1);
+        drop(yielded);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn drops_unyielded() {
+        let rc = Rc::new(());
+        let mut fl = Freelist::from(vec![rc.clone(), rc.clone(), rc.clone(), rc.clone()]);
+        fl.remove(2);
+
+        let mut iter = IntoIterFl::new(fl);
+        let front = iter.next().unwrap();
+        let back = iter.next_back().unwrap();
+        drop(iter);
+        assert_eq!(Rc::strong_count(&rc), 3);
+
+        drop((front, back));
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn panicking_drop() {
+        let rc = Rc::new(());
+        let fl = Freelist::from([false, true, false].map(|explode| Bomb::new(&rc, explode)));
+
+        let mut iter = IntoIterFl::new(fl);
+        let first = iter.next().unwrap();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| drop(iter)));
+
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&rc), 2);
+        drop(first);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn covariant() {
+        // Fails to compile if the iterator is invariant in `T`.
+        fn shorten<'a>(
+            iter: <Freelist<&'static str> as IntoIterator>::IntoIter
+        ) -> <Freelist<&'a str> as IntoIterator>::IntoIter {
+            iter
+        }
+
+        let mut iter = shorten(Freelist::from(["a"]).into_iter());
+        assert_eq!(iter.next(), Some("a"));
     }
-}
\ No newline at end of file
+}
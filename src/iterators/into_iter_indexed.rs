@@ -0,0 +1,95 @@
+use std::iter::{ExactSizeIterator, FusedIterator};
+
+use crate::Freelist;
+
+use super::IntoIterFl;
+
+pub struct IntoIterIndexedFl<T> {
+    inner: IntoIterFl<T>
+}
+
+impl<T> IntoIterIndexedFl<T> {
+    #[inline]
+    pub(crate) fn new(freelist: Freelist<T>) -> Self {
+        Self { inner: IntoIterFl::new(freelist) }
+    }
+}
+
+impl<T> Iterator for IntoIterIndexedFl<T> {
+    type Item = (usize, T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_ptr()
+            .map(|slot| (self.inner.index_of(slot), unsafe { slot.read().into_value_unchecked() }))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { self.inner.size_hint() }
+}
+
+impl<T> DoubleEndedIterator for IntoIterIndexedFl<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back_ptr()
+            .map(|slot| (self.inner.index_of(slot), unsafe { slot.read().into_value_unchecked() }))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIterIndexedFl<T> {}
+
+impl<T> FusedIterator for IntoIterIndexedFl<T> {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next() {
+        let mut fl = Freelist::from([0, 1, 1, 2]);
+        fl.remove(0);
+        fl.remove(2);
+        let mut iter = IntoIterIndexedFl::new(fl);
+
+        assert_eq!(iter.next(), Some((1, 1)));
+        assert_eq!(iter.next(), Some((3, 2)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn next_back() {
+        let mut fl = Freelist::from([0, 1, 1, 2]);
+        fl.remove(0);
+        fl.remove(2);
+        let mut iter = IntoIterIndexedFl::new(fl);
+
+        assert_eq!(iter.next_back(), Some((3, 2)));
+        assert_eq!(iter.next_back(), Some((1, 1)));
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn drops_unyielded() {
+        let rc = std::rc::Rc::new(());
+        let mut fl = Freelist::from(vec![rc.clone(), rc.clone(), rc.clone()]);
+        fl.remove(0);
+
+        let mut iter = IntoIterIndexedFl::new(fl);
+        let (index, value) = iter.next().unwrap();
+        assert_eq!(index, 1);
+        drop(iter);
+        assert_eq!(std::rc::Rc::strong_count(&rc), 2);
+        drop(value);
+        assert_eq!(std::rc::Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn covariant() {
+        // Fails to compile if the iterator is invariant in `T`.
+        fn shorten<'a>(iter: IntoIterIndexedFl<&'static str>) -> IntoIterIndexedFl<&'a str> { iter }
+
+        let mut iter = shorten(Freelist::from(["a"]).into_iter_indexed());
+        assert_eq!(iter.next(), Some((0, "a")));
+    }
+}
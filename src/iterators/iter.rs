@@ -1,12 +1,12 @@
-use std::{iter::FusedIterator, marker::PhantomData};
+use std::{iter::{ExactSizeIterator, FusedIterator}, marker::PhantomData};
 
 use crate::Slot;
 
-use super::size_hint;
-
 pub struct IterFl<'a, T: 'a> {
     start: *const Slot<T>,
     end: *const Slot<T>,
+    origin: *const Slot<T>,
+    remaining: usize,
     _marker: PhantomData<&'a T>
 }
 
@@ -14,53 +14,82 @@ pub struct IterFl<'a, T: 'a> {
 impl<'a, T: 'a> IterFl<'a, T> {
 
     #[inline]
-    pub(crate) const fn new(slice: &[Slot<T>]) -> Self {
+    pub(crate) const fn new(slice: &[Slot<T>], filled: usize) -> Self {
         let start = slice.as_ptr();
         Self {
             start,
             end: match slice.len() {
                 0 => start,
-                count @ _ => unsafe { start.add(count) }
+                count => unsafe { start.add(count) }
             },
+            origin: start,
+            remaining: filled,
             _marker: PhantomData,
         }
     }
-}
-
-
-impl<'a, T: 'a> Iterator for IterFl<'a, T> {
-    type Item = &'a T;
 
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Advances the front of the iterator to the next slot containing a value.
+    #[inline]
+    pub(super) fn next_ptr(&mut self) -> Option<*const Slot<T>> {
         while self.start < self.end {
             let curr = self.start;
             unsafe {
                 self.start = curr.add(1);
-                if let Slot::Value(value) = &*curr { return Some(value) }
+                if (*curr).is_value() {
+                    self.remaining -= 1;
+                    return Some(curr)
+                }
             }
         }
+        None
+    }
 
+    /// Advances the back of the iterator to the previous slot containing a value.
+    #[inline]
+    pub(super) fn next_back_ptr(&mut self) -> Option<*const Slot<T>> {
+        while self.start < self.end {
+            unsafe {
+                self.end = self.end.offset(-1);
+                if (*self.end).is_value() {
+                    self.remaining -= 1;
+                    return Some(self.end)
+                }
+            }
+        }
         None
     }
 
+    /// Returns the freelist index of a slot yielded by this iterator.
+    #[inline(always)]
+    pub(super) fn index_of(&self, slot: *const Slot<T>) -> usize {
+        unsafe { slot.offset_from(self.origin) as usize }
+    }
+}
+
+
+impl<'a, T: 'a> Iterator for IterFl<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_ptr().map(|slot| unsafe { (*slot).as_value_unchecked() })
+    }
+
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        size_hint::<T>(self.start as usize, self.end as usize)
+        (self.remaining, Some(self.remaining))
     }
 }
 
 impl<'a, T: 'a> DoubleEndedIterator for IterFl<'a, T> {
+    #[inline]
     fn next_back(&mut self) -> Option<Self::Item> {
-        while self.start < self.end {
-            unsafe {
-                self.end = self.end.offset(-1);
-                if let Slot::Value(value) = &*self.end { return Some(value) }
-            }
-        }
-        None
+        self.next_back_ptr().map(|slot| unsafe { (*slot).as_value_unchecked() })
     }
 }
 
+impl<'a, T: 'a> ExactSizeIterator for IterFl<'a, T> {}
+
 impl<'a, T: 'a> FusedIterator for IterFl<'a, T> {}
 
 impl<'a, T: 'a> Drop for IterFl<'a, T> {
@@ -76,13 +105,13 @@ mod tests {
 
     #[test]
     fn next() {
-        let mut iter = IterFl::new(SLICE);
+        let mut iter = IterFl::new(SLICE, 2);
 
         assert_eq!(iter.next(), Some(&1)); 
         assert_eq!(iter.next(), Some(&2));
         assert_eq!(iter.next(), None);
 
-        let mut iter = IterFl::new(ALL_SLICE);
+        let mut iter = IterFl::new(ALL_SLICE, 3);
         for i in [0, 1, 2] { 
             assert_eq!(iter.next(), Some(&i));
         }
@@ -91,13 +120,13 @@ mod tests {
 
     #[test]
     fn next_back() {
-        let mut iter = IterFl::new(SLICE);
+        let mut iter = IterFl::new(SLICE, 2);
 
         assert_eq!(iter.next_back(), Some(&2)); 
         assert_eq!(iter.next_back(), Some(&1));
         assert_eq!(iter.next_back(), None); 
 
-        let mut iter = IterFl::new(ALL_SLICE);
+        let mut iter = IterFl::new(ALL_SLICE, 3);
         for i in [2, 1, 0] { 
             assert_eq!(iter.next_back(), Some(&i));
         }
@@ -106,10 +135,35 @@ mod tests {
 
     #[test]
     fn size_hint() {
-        let mut iter = IterFl::new(SLICE);
+        let mut iter = IterFl::new(SLICE, 2);
+
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        iter.next();
+        assert_eq!(iter.size_hint(), (1, Some(1)));
+    }
 
-        assert_eq!(iter.size_hint(), (4, Some(4)));
+    #[test]
+    fn len() {
+        let mut iter = IterFl::new(SLICE, 2);
+
+        assert_eq!(iter.len(), 2);
+        iter.next_back();
+        assert_eq!(iter.len(), 1);
         iter.next();
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn zero_sized() {
+        let slice: &[Slot<()>] = &[Slot::Value(()), Slot::Next(3), Slot::Value(()), Slot::Empty];
+        let mut iter = IterFl::new(slice, 2);
+
         assert_eq!(iter.size_hint(), (2, Some(2)));
+        assert_eq!(iter.next(), Some(&()));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next_back(), Some(&()));
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,84 @@
+use std::iter::{ExactSizeIterator, FusedIterator};
+
+use crate::Slot;
+
+use super::IterFl;
+
+pub struct IterIndexedFl<'a, T: 'a> {
+    inner: IterFl<'a, T>,
+    offset: usize
+}
+
+impl<'a, T: 'a> IterIndexedFl<'a, T> {
+
+    #[inline]
+    pub(crate) const fn new(slice: &[Slot<T>], filled: usize) -> Self {
+        Self::with_offset(slice, filled, 0)
+    }
+
+    /// Constructs the iterator over a subslice of the freelist starting at index `offset`.
+    #[inline]
+    pub(crate) const fn with_offset(slice: &[Slot<T>], filled: usize, offset: usize) -> Self {
+        Self { inner: IterFl::new(slice, filled), offset }
+    }
+}
+
+impl<'a, T: 'a> Iterator for IterIndexedFl<'a, T> {
+    type Item = (usize, &'a T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_ptr()
+            .map(|slot| (self.offset + self.inner.index_of(slot), unsafe { (*slot).as_value_unchecked() }))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { self.inner.size_hint() }
+}
+
+impl<'a, T: 'a> DoubleEndedIterator for IterIndexedFl<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back_ptr()
+            .map(|slot| (self.offset + self.inner.index_of(slot), unsafe { (*slot).as_value_unchecked() }))
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for IterIndexedFl<'a, T> {}
+
+impl<'a, T: 'a> FusedIterator for IterIndexedFl<'a, T> {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static SLICE: &[Slot<i32>] = &[Slot::Empty, Slot::Value(1), Slot::Next(0), Slot::Value(2)];
+
+    #[test]
+    fn next() {
+        let mut iter = IterIndexedFl::new(SLICE, 2);
+
+        assert_eq!(iter.next(), Some((1, &1)));
+        assert_eq!(iter.next(), Some((3, &2)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn next_back() {
+        let mut iter = IterIndexedFl::new(SLICE, 2);
+
+        assert_eq!(iter.next_back(), Some((3, &2)));
+        assert_eq!(iter.next_back(), Some((1, &1)));
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn mixed() {
+        let mut iter = IterIndexedFl::new(SLICE, 2);
+
+        assert_eq!(iter.next_back(), Some((3, &2)));
+        assert_eq!(iter.next(), Some((1, &1)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+}
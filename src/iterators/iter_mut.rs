@@ -1,12 +1,12 @@
-use std::{iter::FusedIterator, marker::PhantomData};
+use std::{iter::{ExactSizeIterator, FusedIterator}, marker::PhantomData};
 
 use crate::Slot;
 
-use super::size_hint;
-
 pub struct IterMutFl<'a, T: 'a> {
     start: *mut Slot<T>,
     end: *mut Slot<T>,
+    origin: *mut Slot<T>,
+    remaining: usize,
     _marker: PhantomData<&'a mut T>
 }
 
@@ -14,51 +14,81 @@ pub struct IterMutFl<'a, T: 'a> {
 impl<'a, T: 'a> IterMutFl<'a, T> {
 
     #[inline]
-    pub(crate) const fn new(slice: &mut [Slot<T>]) -> Self {
+    pub(crate) const fn new(slice: &mut [Slot<T>], filled: usize) -> Self {
         let start = slice.as_mut_ptr();
         Self {
             start,
             end: match slice.len() {
                 0 => start,
-                count @ _ => unsafe { start.add(count) }
+                count => unsafe { start.add(count) }
             },
+            origin: start,
+            remaining: filled,
             _marker: PhantomData,
         }
     }
-}
-
-impl<'a, T: 'a> Iterator for IterMutFl<'a, T> {
-    type Item = &'a mut T;
 
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Advances the front of the iterator to the next slot containing a value.
+    #[inline]
+    pub(super) fn next_ptr(&mut self) -> Option<*mut Slot<T>> {
         while self.start < self.end {
             let curr = self.start;
             unsafe {
-                self.start = self.start.add(1);
-                if let Slot::Value(value) = &mut *curr { return Some(value) }
+                self.start = curr.add(1);
+                if (*curr).is_value() {
+                    self.remaining -= 1;
+                    return Some(curr)
+                }
             }
         }
         None
     }
 
+    /// Advances the back of the iterator to the previous slot containing a value.
     #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        size_hint::<T>(self.start as usize, self.end as usize)
-    }
-}
-
-impl<'a, T: 'a> DoubleEndedIterator for IterMutFl<'a, T> {
-    fn next_back(&mut self) -> Option<Self::Item> {
+    pub(super) fn next_back_ptr(&mut self) -> Option<*mut Slot<T>> {
         while self.start < self.end {
             unsafe {
                 self.end = self.end.offset(-1);
-                if let Slot::Value(value) = &mut *self.end { return Some(value) }
+                if (*self.end).is_value() {
+                    self.remaining -= 1;
+                    return Some(self.end)
+                }
             }
         }
         None
     }
+
+    /// Returns the freelist index of a slot yielded by this iterator.
+    #[inline(always)]
+    pub(super) fn index_of(&self, slot: *mut Slot<T>) -> usize {
+        unsafe { slot.offset_from(self.origin) as usize }
+    }
+}
+
+impl<'a, T: 'a> Iterator for IterMutFl<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_ptr().map(|slot| unsafe { (*slot).as_value_unchecked_mut() })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
 }
 
+impl<'a, T: 'a> DoubleEndedIterator for IterMutFl<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next_back_ptr().map(|slot| unsafe { (*slot).as_value_unchecked_mut() })
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for IterMutFl<'a, T> {}
+
 impl<'a, T: 'a> FusedIterator for IterMutFl<'a, T> {}
 
 impl<'a, T: 'a> Drop for IterMutFl<'a, T> {
@@ -73,14 +103,14 @@ mod tests {
     #[test]
     fn next() {
         let slice = &mut [Slot::Empty, Slot::Value(1), Slot::Next(0), Slot::Value(2)];
-        let mut iter = IterMutFl::new(slice);
+        let mut iter = IterMutFl::new(slice, 2);
 
         assert_eq!(iter.next(), Some(&mut 1)); 
         assert_eq!(iter.next(), Some(&mut 2));
         assert_eq!(iter.next(), None); 
 
         let slice = &mut [Slot::Value(0), Slot::Value(1), Slot::Value(2)];
-        let mut iter = IterMutFl::new(slice);
+        let mut iter = IterMutFl::new(slice, 3);
         for mut i in [0, 1, 2] { 
             assert_eq!(iter.next(), Some(&mut i));
         }
@@ -90,14 +120,14 @@ mod tests {
         #[test]
     fn next_back() {
         let slice = &mut [Slot::Empty, Slot::Value(1), Slot::Next(0), Slot::Value(2)];
-        let mut iter = IterMutFl::new(slice);
+        let mut iter = IterMutFl::new(slice, 2);
 
         assert_eq!(iter.next_back(), Some(&mut 2)); 
         assert_eq!(iter.next_back(), Some(&mut 1));
         assert_eq!(iter.next_back(), None);
 
         let slice = &mut [Slot::Value(0), Slot::Value(1), Slot::Value(2)];
-        let mut iter = IterMutFl::new(slice);
+        let mut iter = IterMutFl::new(slice, 3);
         for mut i in [2, 1, 0] { 
             assert_eq!(iter.next_back(), Some(&mut i));
         }
@@ -107,16 +137,16 @@ mod tests {
     #[test]
     fn size_hint() {
         let slice = &mut [Slot::Empty, Slot::Value(1), Slot::Next(0), Slot::Value(2)];
-        let mut iter = IterMutFl::new(slice);
-        assert_eq!(iter.size_hint(), (4, Some(4)));
-        iter.next();
+        let mut iter = IterMutFl::new(slice, 2);
         assert_eq!(iter.size_hint(), (2, Some(2)));
+        iter.next();
+        assert_eq!(iter.size_hint(), (1, Some(1)));
     }
 
     #[test]
     fn update_value() {
         let slice = &mut [Slot::Empty, Slot::Value(1), Slot::Next(0), Slot::Value(2)];
-        let mut iter = IterMutFl::new(slice);
+        let mut iter = IterMutFl::new(slice, 2);
         *iter.next().unwrap() = 11;
         assert_eq!(slice[1], Slot::Value(11));
     }
@@ -0,0 +1,85 @@
+use std::iter::{ExactSizeIterator, FusedIterator};
+
+use crate::Slot;
+
+use super::IterMutFl;
+
+pub struct IterMutIndexedFl<'a, T: 'a> {
+    inner: IterMutFl<'a, T>,
+    offset: usize
+}
+
+impl<'a, T: 'a> IterMutIndexedFl<'a, T> {
+
+    #[inline]
+    pub(crate) const fn new(slice: &mut [Slot<T>], filled: usize) -> Self {
+        Self::with_offset(slice, filled, 0)
+    }
+
+    /// Constructs the iterator over a subslice of the freelist starting at index `offset`.
+    #[inline]
+    pub(crate) const fn with_offset(slice: &mut [Slot<T>], filled: usize, offset: usize) -> Self {
+        Self { inner: IterMutFl::new(slice, filled), offset }
+    }
+}
+
+impl<'a, T: 'a> Iterator for IterMutIndexedFl<'a, T> {
+    type Item = (usize, &'a mut T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_ptr()
+            .map(|slot| (self.offset + self.inner.index_of(slot), unsafe { (*slot).as_value_unchecked_mut() }))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { self.inner.size_hint() }
+}
+
+impl<'a, T: 'a> DoubleEndedIterator for IterMutIndexedFl<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back_ptr()
+            .map(|slot| (self.offset + self.inner.index_of(slot), unsafe { (*slot).as_value_unchecked_mut() }))
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for IterMutIndexedFl<'a, T> {}
+
+impl<'a, T: 'a> FusedIterator for IterMutIndexedFl<'a, T> {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next() {
+        let slice = &mut [Slot::Empty, Slot::Value(1), Slot::Next(0), Slot::Value(2)];
+        let mut iter = IterMutIndexedFl::new(slice, 2);
+
+        assert_eq!(iter.next(), Some((1, &mut 1)));
+        assert_eq!(iter.next(), Some((3, &mut 2)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn next_back() {
+        let slice = &mut [Slot::Empty, Slot::Value(1), Slot::Next(0), Slot::Value(2)];
+        let mut iter = IterMutIndexedFl::new(slice, 2);
+
+        assert_eq!(iter.next_back(), Some((3, &mut 2)));
+        assert_eq!(iter.next_back(), Some((1, &mut 1)));
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn update_value() {
+        let slice = &mut [Slot::Empty, Slot::Value(1), Slot::Next(0), Slot::Value(2)];
+        for (index, value) in IterMutIndexedFl::new(slice, 2) {
+            *value = index as i32 * 10;
+        }
+        assert_eq!(slice[1], Slot::Value(10));
+        assert_eq!(slice[3], Slot::Value(30));
+    }
+}
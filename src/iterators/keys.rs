@@ -0,0 +1,65 @@
+use std::iter::{ExactSizeIterator, FusedIterator};
+
+use crate::Slot;
+
+use super::IterFl;
+
+pub struct KeysFl<'a, T: 'a> {
+    inner: IterFl<'a, T>
+}
+
+impl<'a, T: 'a> KeysFl<'a, T> {
+
+    #[inline]
+    pub(crate) const fn new(slice: &[Slot<T>], filled: usize) -> Self {
+        Self { inner: IterFl::new(slice, filled) }
+    }
+}
+
+impl<'a, T: 'a> Iterator for KeysFl<'a, T> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_ptr().map(|slot| self.inner.index_of(slot))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { self.inner.size_hint() }
+}
+
+impl<'a, T: 'a> DoubleEndedIterator for KeysFl<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back_ptr().map(|slot| self.inner.index_of(slot))
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for KeysFl<'a, T> {}
+
+impl<'a, T: 'a> FusedIterator for KeysFl<'a, T> {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static SLICE: &[Slot<i32>] = &[Slot::Empty, Slot::Value(1), Slot::Next(0), Slot::Value(2)];
+
+    #[test]
+    fn next() {
+        let mut iter = KeysFl::new(SLICE, 2);
+
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn next_back() {
+        let mut iter = KeysFl::new(SLICE, 2);
+
+        assert_eq!(iter.next_back(), Some(3));
+        assert_eq!(iter.next_back(), Some(1));
+        assert_eq!(iter.next_back(), None);
+    }
+}
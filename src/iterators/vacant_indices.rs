@@ -0,0 +1,68 @@
+use std::iter::{ExactSizeIterator, FusedIterator};
+
+use crate::Slot;
+
+pub struct VacantIndicesFl<'a, T: 'a> {
+    slots: &'a [Slot<T>],
+    next: &'a Slot<T>,
+    remaining: usize
+}
+
+impl<'a, T: 'a> VacantIndicesFl<'a, T> {
+
+    #[inline]
+    pub(crate) const fn new(slots: &'a [Slot<T>], next: &'a Slot<T>, free: usize) -> Self {
+        Self { slots, next, remaining: free }
+    }
+}
+
+impl<'a, T: 'a> Iterator for VacantIndicesFl<'a, T> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next {
+            Slot::Next(index) if self.remaining > 0 => {
+                self.remaining -= 1;
+                self.next = &self.slots[*index];
+                Some(*index)
+            },
+            _ => None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for VacantIndicesFl<'a, T> {}
+
+impl<'a, T: 'a> FusedIterator for VacantIndicesFl<'a, T> {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static SLICE: &[Slot<i32>] = &[Slot::Next(3), Slot::Value(1), Slot::Empty, Slot::Next(2), Slot::Value(4)];
+
+    #[test]
+    fn next() {
+        let mut iter = VacantIndicesFl::new(SLICE, &Slot::Next(0), 3);
+
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn empty() {
+        let mut iter = VacantIndicesFl::new(SLICE, &Slot::Empty, 0);
+
+        assert_eq!(iter.next(), None);
+    }
+}
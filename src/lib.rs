@@ -1,16 +1,38 @@
 #![doc = include_str!("../doc/lib.md")]
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+mod cursor;
+mod debug;
+mod error;
 mod iterators;
 mod slot;
-
-use std::{hint::unreachable_unchecked, mem::replace, ops::{Index, IndexMut}};
-
-use slot::Slot;
+mod view;
+#[cfg(feature = "rayon")]
+mod par;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+pub mod raw;
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+use std::{collections::BTreeMap, hash::{Hash, Hasher}, hint::unreachable_unchecked, mem::replace, ops::{Index, IndexMut, RangeBounds, Bound}, slice::GetDisjointMutError};
+
+use slot::{Slot, check_free_chain, count_values};
 use iterators::*;
 
+pub use cursor::{CursorMut, Placement};
+pub use debug::DebugLayout;
+pub use error::{DuplicateIndexError, GetDisjointError, InvariantViolation};
+pub use view::FreelistViewMut;
+
 
 #[doc = include_str!("../doc/freelist.md")]
-#[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(::rkyv::Archive, ::rkyv::Serialize), rkyv(bytecheck(verify)))]
 pub struct Freelist<T> {
     slots: Vec<Slot<T>>,
     next: Slot<T>,
@@ -65,6 +87,56 @@ impl<T> Freelist<T> {
         }
     }
 
+    /// Constructs a freelist from `(index, value)` pairs, placing each value at its index.
+    ///
+    /// The freelist's size is one past the largest index, with every index not given
+    /// left as a free slot.  Free slots are reused in ascending index order.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DuplicateIndexError`] if the same index appears more than once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is `usize::MAX`, or if the new capacity exceeds `isize::MAX` *bytes*.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from_sparse([(3, 'd'), (1, 'b')]).unwrap();
+    ///
+    /// assert_eq!(fl.size(), 4);
+    /// assert_eq!(fl.get(1), Some(&'b'));
+    /// assert_eq!(fl.get(2), None);
+    /// assert_eq!(fl.push('a'), 0);
+    ///
+    /// assert_eq!(Freelist::from_sparse([(0, 'a'), (0, 'b')]).unwrap_err().index(), 0);
+    /// ```
+    ///
+    /// # Time Complexity
+    ///
+    /// *O*(n), where `n` is the resulting size of the freelist. See [`size`](Freelist::size).
+    pub fn from_sparse<I>(iter: I) -> Result<Self, DuplicateIndexError>
+    where
+        I: IntoIterator<Item = (usize, T)>
+    {
+        let mut fl = Self::new();
+        for (index, value) in iter {
+            if index >= fl.slots.len() {
+                let size = index.checked_add(1).expect("index out of range for a freelist");
+                fl.slots.resize_with(size, || Slot::Empty);
+            }
+            if replace(&mut fl.slots[index], Slot::Value(value)).is_value() {
+                return Err(DuplicateIndexError { index })
+            }
+            fl.filled_length += 1;
+        }
+        fl.sort_free_chain();
+        Ok(fl)
+    }
+
     /// Appends an element to the first free slot (or back of the list)
     /// and returns the index of insertion.
     /// 
@@ -106,7 +178,7 @@ impl<T> Freelist<T> {
     pub fn push(&mut self, value: T) -> usize {
         self.filled_length += 1;
         let src = Slot::Value(value);
-        match self.next {
+        let index = match self.next {
             Slot::Next(index) => unsafe {
                 self.next = replace(self.slots.get_unchecked_mut(index), src);
                 index
@@ -116,7 +188,9 @@ impl<T> Freelist<T> {
                 self.filled_length - 1
             },
             _ => unsafe { unreachable_unchecked() }
-        }
+        };
+        self.debug_validate();
+        index
     }
 
     /// Returns the next available index.
@@ -167,14 +241,16 @@ impl<T> Freelist<T> {
 
         // The data struture guarantees the following operations are valid.
         // Next(index) -> self.next -> Value(value) -> return Some(value)
-        match &mut self.slots[index] {
+        let removed = match &mut self.slots[index] {
             value @ Slot::Value(_) => unsafe {
                 self.filled_length -= 1;
                 replace(value, replace(&mut self.next, Slot::Next(index)))
-                    .to_some_unchecked()
+                    .into_some_unchecked()
             },
             _ => None
-        }
+        };
+        self.debug_validate();
+        removed
     }
 
 
@@ -204,12 +280,14 @@ impl<T> Freelist<T> {
     /// ```
     #[inline]
     pub unsafe fn remove_unchecked(&mut self, index: usize) -> T {
-        unsafe {
+        let value = unsafe {
             self.filled_length -= 1;
             let slot = self.slots.get_unchecked_mut(index);
             replace(slot, replace(&mut self.next, Slot::Next(index)))
-                .to_value_unchecked()   
-        }
+                .into_value_unchecked()
+        };
+        self.debug_validate();
+        value
     }
 
     /// Returns the number of filled slots in the list.
@@ -281,14 +359,96 @@ impl<T> Freelist<T> {
     #[inline]
     pub fn capacity(&self) -> usize { self.slots.capacity() }
 
+    /// Returns the number of values that can be pushed without reallocating, counting
+    /// both free slots and allocated slots that are not yet in use.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use fffl::Freelist;
+    /// 
+    /// let mut fl = Freelist::from([1, 2, 3]);
+    /// let _ = fl.remove(1);
+    /// assert_eq!(fl.available_capacity(), fl.capacity() - 2);
+    /// ```
+    #[inline]
+    pub fn available_capacity(&self) -> usize { self.slots.capacity() - self.filled_length }
+
     #[inline]
     /// Clears the freelist, removing all values.
     pub fn clear(&mut self) {
-        self.slots.clear();
+        // Reset the chain first, so the freelist remains valid should a value panic while dropping.
         self.next = Slot::Empty;
         self.filled_length = 0;
+        self.slots.clear();
+        self.debug_validate();
     }
 
+    /// Returns an iterator over the indices of all free slots, in the order they will
+    /// be reused by [`push`](Freelist::push).
+    ///
+    /// The iterator walks the chain of free slots, so the first index yielded is
+    /// always [`next_available`](Freelist::next_available) (if any slots are free).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([1, 2, 3, 4, 5]);
+    /// let _ = fl.remove(1); // Some(2)
+    /// let _ = fl.remove(4); // Some(5)
+    /// let _ = fl.remove(2); // Some(3)
+    ///
+    /// assert_eq!(fl.vacant_indices().collect::<Vec<_>>(), [2, 4, 1]);
+    /// ```
+    pub fn vacant_indices(&self) -> VacantIndicesFl<'_, T> {
+        VacantIndicesFl::new(&self.slots, &self.next, self.free())
+    }
+
+    /// Returns an iterator over each maximal range of consecutive free slots, in index order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([1, 2, 3, 4, 5, 6]);
+    /// for index in [0, 3, 2, 5] { let _ = fl.remove(index); }
+    ///
+    /// assert_eq!(fl.free_runs().collect::<Vec<_>>(), [0..1, 2..4, 5..6]);
+    /// assert_eq!(fl.free_runs().next_back(), Some(5..6));
+    /// ```
+    pub fn free_runs(&self) -> FreeRunsFl<'_, T> { FreeRunsFl::new(&self.slots) }
+
+    /// Removes all values from the freelist, returning them alongside their indices
+    /// as an iterator.
+    ///
+    /// The freelist is left empty, keeping its allocated capacity, with the chain
+    /// of free slots reset.  Values that are not yielded are dropped along with the
+    /// iterator.
+    ///
+    /// If the iterator is leaked (e.g. with [`mem::forget`](std::mem::forget)), the freelist
+    /// is still left empty, however any values that were not yielded are leaked as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from(['a', 'b', 'c', 'd']);
+    /// let _ = fl.remove(1); // Some('b')
+    ///
+    /// let drained: Vec<(usize, char)> = fl.drain().collect();
+    /// assert_eq!(drained, [(0, 'a'), (2, 'c'), (3, 'd')]);
+    ///
+    /// assert_eq!(fl.filled(), 0);
+    /// assert_eq!(fl.size(), 0);
+    /// assert!(fl.capacity() >= 4);
+    /// assert_eq!(fl.push('e'), 0);
+    /// ```
+    pub fn drain(&mut self) -> DrainFl<'_, T> { DrainFl::new(self) }
+
     /// Converts the freelist into a `Vec<T>`, skipping free slots.
     /// 
     /// # Examples
@@ -306,9 +466,12 @@ impl<T> Freelist<T> {
         self.into_iter().collect() 
     }
 
-    /// Reserves the minimum capacity for at least `additional` more elements to
-    /// be inserted in the given `Freelist<T>`.  The function will account for
-    /// previously freed slots.
+    /// Reserves capacity for at least `additional` more values to be pushed into the
+    /// given `Freelist<T>`, so that the next `additional` calls to [`push`](Freelist::push)
+    /// won't reallocate.  Free slots and spare capacity are counted towards `additional`,
+    /// and the freelist may reserve more space to avoid frequent reallocations.
+    /// 
+    /// Does nothing if [`available_capacity`](Freelist::available_capacity) is already sufficient.
     /// 
     /// # Panics
     /// 
@@ -322,9 +485,117 @@ impl<T> Freelist<T> {
     /// let mut fl = Freelist::from([1]);
     /// fl.reserve(10);
     /// assert!(fl.capacity() >= 11);
+    /// 
+    /// let _ = fl.remove(0);
+    /// fl.reserve(1); // Reuses the free slot
+    /// assert!(fl.available_capacity() >= 1);
     /// ```
     pub fn reserve(&mut self, additional: usize) {
-        self.slots.reserve_exact(additional - self.free());
+        self.slots.reserve(additional.saturating_sub(self.free()));
+    }
+
+    /// Reserves the minimum capacity for at least `additional` more values to be pushed
+    /// into the given `Freelist<T>`, so that the next `additional` calls to
+    /// [`push`](Freelist::push) won't reallocate.  Free slots and spare capacity are
+    /// counted towards `additional`.
+    /// 
+    /// Unlike [`reserve`](Freelist::reserve), this will not deliberately over-allocate,
+    /// although the allocator may still give the freelist more space than requested.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if the new capacity exceeds `isize::MAX` _bytes_.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use fffl::Freelist;
+    /// 
+    /// let mut fl = Freelist::from([1, 2, 3]);
+    /// let _ = fl.remove(1);
+    /// fl.reserve_exact(10);
+    /// assert!(fl.available_capacity() >= 10);
+    /// ```
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.slots.reserve_exact(additional.saturating_sub(self.free()));
+    }
+
+    /// Retains only the values specified by the predicate.
+    ///
+    /// In other words, removes all values `v` for which `f(index, &mut v)` returns `false`.
+    /// Values are visited once, in ascending index order, and the predicate may modify
+    /// any value it visits.  All remaining values keep their indices.
+    ///
+    /// Freed slots are added to the chain of free slots exactly as if [`remove`](Freelist::remove)
+    /// had been called on each in ascending index order, meaning the highest freed index
+    /// is reused first.
+    ///
+    /// Should the predicate panic, the values removed up to that point stay removed and
+    /// the freelist remains valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([1, 2, 3, 4, 5]);
+    /// fl.retain(|_, v| *v % 2 == 1);
+    ///
+    /// assert_eq!(fl.to_vec(), [1, 3, 5]);
+    /// ```
+    /// The index of each value is available to the predicate:
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from(['a', 'b', 'c', 'd']);
+    /// fl.retain(|index, _| index < 2);
+    ///
+    /// assert_eq!(fl.push('e'), 3);
+    /// assert_eq!(fl.push('f'), 2);
+    /// ```
+    ///
+    /// # Time Complexity
+    ///
+    /// *O*(n), where `n` is the size of the freelist. See [`size`](Freelist::size).
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(usize, &mut T) -> bool
+    {
+        for index in 0..self.slots.len() {
+            if let Slot::Value(value) = &mut self.slots[index] && !f(index, value) {
+                drop(unsafe { self.remove_unchecked(index) });
+            }
+        }
+    }
+
+    /// Creates an iterator which uses a predicate to determine whether a value should be removed.
+    ///
+    /// If `f(index, &mut v)` returns `true`, the value is removed and yielded alongside its index.
+    /// Otherwise the value remains in the freelist.  The predicate may modify any value it visits.
+    ///
+    /// The iterator is lazy: values are only visited as the iterator is advanced, and any
+    /// values not yet visited when it is dropped remain in the freelist untouched.
+    ///
+    /// Freed slots are added to the chain of free slots in the order they are yielded,
+    /// exactly as if [`remove`](Freelist::remove) had been called on each.  Should the
+    /// predicate panic, the freelist remains valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([1, 2, 3, 4, 5, 6]);
+    /// let evens: Vec<(usize, i32)> = fl.extract_if(|_, v| *v % 2 == 0).collect();
+    ///
+    /// assert_eq!(evens, [(1, 2), (3, 4), (5, 6)]);
+    /// assert_eq!(fl.to_vec(), [1, 3, 5]);
+    /// ```
+    pub fn extract_if<F>(&mut self, f: F) -> ExtractIfFl<'_, T, F>
+    where
+        F: FnMut(usize, &mut T) -> bool
+    {
+        ExtractIfFl::new(self, f)
     }
 
     /// Swaps all values to the front of the freelist.
@@ -376,18 +647,55 @@ impl<T> Freelist<T> {
                 }
             };
 
-            // In benchmarking this is a few percent faster than using
-            // std::mem::swap(hole, plug).  It's safe because the function will 
-            // truncate the duplicated values at any given 'plug' anyway. Thus 
-            // we avoid violating memory safety.
-            unsafe {
-                std::ptr::copy_nonoverlapping(plug, hole, 1)
-            }
+            // Moves the value into the hole, leaving the free slot at the back
+            // where it's truncated.  Each value is owned by exactly one slot
+            // throughout, so none are dropped by the truncation.
+            std::mem::swap(hole, plug);
         }
 
         self.slots.truncate(self.filled_length);
         self.next = Slot::Empty;
+        self.debug_validate();
+    }
 
+    /// Rebuilds the chain of free slots so that they are reused in ascending index order.
+    ///
+    /// Freed slots are normally reused last-in-first-out, meaning a long-lived freelist
+    /// may [`push`](Freelist::push) into high indices while holes near the front sit unused.
+    /// After sorting, subsequent pushes will fill the lowest free slots first.
+    ///
+    /// Unlike [`compactify`](Freelist::compactify), no values are moved and all
+    /// previously returned indices remain valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([1, 2, 3, 4, 5]);
+    /// let _ = fl.remove(1); // Some(2)
+    /// let _ = fl.remove(3); // Some(4)
+    ///
+    /// assert_eq!(fl.next_available(), 3);
+    ///
+    /// fl.sort_free_chain();
+    ///
+    /// assert_eq!(fl.push(12), 1);
+    /// assert_eq!(fl.push(14), 3);
+    /// ```
+    ///
+    /// # Time Complexity
+    ///
+    /// *O*(n), where `n` is the size of the freelist. See [`size`](Freelist::size).
+    pub fn sort_free_chain(&mut self) {
+        let mut next = Slot::Empty;
+        for (index, slot) in self.slots.iter_mut().enumerate().rev() {
+            if !slot.is_value() {
+                *slot = replace(&mut next, Slot::Next(index));
+            }
+        }
+        self.next = next;
+        self.debug_validate();
     }
 
 
@@ -462,6 +770,77 @@ impl<T> Freelist<T> {
         unsafe { self.slots.get_unchecked_mut(index).as_value_unchecked_mut() }
     }
 
+    /// Returns mutable references to many values at once.
+    ///
+    /// Returns an error if any index is out-of-bounds, refers to a free slot, or if the
+    /// same index was passed more than once.  See [`GetDisjointError`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fffl::{Freelist, GetDisjointError};
+    ///
+    /// let mut fl = Freelist::from([1, 2, 3, 4]);
+    /// let _ = fl.remove(3); // Some(4)
+    ///
+    /// if let Ok([a, b]) = fl.get_disjoint_mut([0, 2]) {
+    ///     std::mem::swap(a, b);
+    /// }
+    /// assert_eq!((fl[0], fl[2]), (3, 1));
+    ///
+    /// assert_eq!(fl.get_disjoint_mut([0, 4]), Err(GetDisjointError::IndexOutOfBounds));
+    /// assert_eq!(fl.get_disjoint_mut([0, 3]), Err(GetDisjointError::VacantSlot));
+    /// assert_eq!(fl.get_disjoint_mut([1, 1]), Err(GetDisjointError::OverlappingIndices));
+    /// ```
+    ///
+    /// # Time Complexity
+    ///
+    /// *O*(N²), where `N` is the number of indices, in order to check for overlapping indices.
+    pub fn get_disjoint_mut<const N: usize>(
+        &mut self,
+        indices: [usize; N]
+    ) -> Result<[&mut T; N], GetDisjointError> {
+        let slots = self.slots.get_disjoint_mut(indices).map_err(|err| match err {
+            GetDisjointMutError::IndexOutOfBounds => GetDisjointError::IndexOutOfBounds,
+            _ => GetDisjointError::OverlappingIndices,
+        })?;
+
+        match slots.iter().all(|slot| slot.is_value()) {
+            true => Ok(slots.map(|slot| unsafe { slot.as_value_unchecked_mut() })),
+            false => Err(GetDisjointError::VacantSlot)
+        }
+    }
+
+    /// Returns mutable references to many values at once, without doing any checks.
+    ///
+    /// See [`get_disjoint_mut`](Freelist::get_disjoint_mut) for a safe alternative.
+    ///
+    /// # Safety
+    ///
+    /// Calling this method with overlapping, out-of-bounds or empty indices is
+    /// [undefined behavior](<https://doc.rust-lang.org/reference/behavior-considered-undefined.html>)
+    /// even if the resulting references are not used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([1, 2, 3]);
+    ///
+    /// let [a, b] = unsafe { fl.get_disjoint_unchecked_mut([0, 2]) };
+    /// *a *= 10;
+    /// *b *= 100;
+    ///
+    /// assert_eq!(fl.to_vec(), [10, 2, 300]);
+    /// ```
+    pub unsafe fn get_disjoint_unchecked_mut<const N: usize>(&mut self, indices: [usize; N]) -> [&mut T; N] {
+        unsafe {
+            self.slots.get_disjoint_unchecked_mut(indices)
+                .map(|slot| slot.as_value_unchecked_mut())
+        }
+    }
+
     /// Returns an iterator over the full freelist.
     /// 
     /// The iterator will skip over freed slots, returning only valid entries from start to end.
@@ -479,7 +858,7 @@ impl<T> Freelist<T> {
     /// assert_eq!(iterator.next(), Some(&8));
     /// assert_eq!(iterator.next(), None);
     /// ```
-    pub fn iter(&self) -> IterFl<'_, T> { IterFl::new(&self.slots) }
+    pub fn iter(&self) -> IterFl<'_, T> { IterFl::new(&self.slots, self.filled_length) }
 
     /// Returns an iterator over the full freelist that allows modifying each value.
     /// 
@@ -497,49 +876,317 @@ impl<T> Freelist<T> {
     /// 
     /// assert_eq!(fl.to_vec(), [2, 4, 8]);
     /// ```
-    pub fn iter_mut(&mut self) -> IterMutFl<'_, T> { IterMutFl::new(&mut self.slots) }
-
-}
-
-impl<T> Default for Freelist<T> {
-    /// Creates an empty `Freelist<T>`.
-    /// 
-    /// The freelist will not allocate until elements are pushed into it.
-    fn default() -> Self {
-        Self { slots: Vec::new(), next: Slot::Empty, filled_length: 0 }
-    }
-}
+    pub fn iter_mut(&mut self) -> IterMutFl<'_, T> { IterMutFl::new(&mut self.slots, self.filled_length) }
 
-impl<T> Index<usize> for Freelist<T> {
-    type Output = T;
+    /// Returns a cursor pointing at the first value of the freelist (or the ghost
+    /// position if the freelist has no values).  See [`CursorMut`].
+    ///
+    /// The cursor allows walking the freelist while removing values and pushing new ones.
+    ///
+    /// # Examples
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([1, 2, 3, 4]);
+    /// let mut cursor = fl.cursor_mut();
+    ///
+    /// while let Some((_, value)) = cursor.current() {
+    ///     if *value % 2 == 0 {
+    ///         let value = cursor.remove_current().unwrap();
+    ///         // The freed slot is reused straight away, so the new value is never revisited.
+    ///         cursor.push(value * 10);
+    ///     } else {
+    ///         cursor.move_next();
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(fl.to_vec(), [1, 20, 3, 40]);
+    /// ```
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T> { CursorMut::new(self) }
 
-    /// Performs the indexing `(container[index])` operation. [Read more](<https://doc.rust-lang.org/std/ops/trait.Index.html#tymethod.index>)
-    /// 
+    /// Returns an iterator over the values within the given range of indices.
+    ///
+    /// The iterator will skip over freed slots, returning only valid entries from start to end.
+    ///
     /// # Panics
-    /// 
-    /// Panics if the index is out of bounds or is an empty slot.
-    /// Use [`get`](Freelist::get) for a safer alternative.
-    #[inline]
-    fn index(&self, index: usize) -> &Self::Output {
-        match &self.slots[index] {
-            Slot::Value(element) => element,
-            _ => panic!("attempted to access an empty slot")
-        }
+    ///
+    /// Panics if the range is out of bounds or its start is greater than its end.
+    ///
+    /// # Examples
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([1, 2, 4, 8, 16]);
+    /// let _ = fl.remove(2); // Some(4)
+    ///
+    /// assert_eq!(fl.range(1..4).collect::<Vec<_>>(), [&2, &8]);
+    /// assert_eq!(fl.range(3..).len(), 2);
+    /// ```
+    ///
+    /// # Time Complexity
+    ///
+    /// Creating the iterator takes *O*(n) time, where `n` is the length of the range, in
+    /// order to count the values it will yield.
+    pub fn range<R: RangeBounds<usize>>(&self, range: R) -> IterFl<'_, T> {
+        let slots = &self.slots[bounds(range)];
+        IterFl::new(slots, count_values(slots))
     }
-}
 
-impl<T> IndexMut<usize> for Freelist<T> {
-
-    /// Performs the mutable indexing `(container[index])` operation. [Read more](<https://doc.rust-lang.org/1.85.1/core/ops/trait.IndexMut.html#tymethod.index_mut>)
-    /// 
+    /// Returns an iterator over the values within the given range of indices that allows
+    /// modifying each value.
+    ///
     /// # Panics
-    /// 
-    /// Panics if the index is out of bounds or is an empty slot.
-    /// Use [`get_mut`](Freelist::get_mut) for a safer alternative.
-    #[inline]
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        match &mut self.slots[index] {
-            Slot::Value(element) => element,
+    ///
+    /// Panics if the range is out of bounds or its start is greater than its end.
+    ///
+    /// # Examples
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([1, 2, 3, 4]);
+    /// for val in fl.range_mut(..2) {
+    ///     *val *= 10;
+    /// }
+    ///
+    /// assert_eq!(fl.to_vec(), [10, 20, 3, 4]);
+    /// ```
+    ///
+    /// # Time Complexity
+    ///
+    /// Creating the iterator takes *O*(n) time, where `n` is the length of the range, in
+    /// order to count the values it will yield.
+    pub fn range_mut<R: RangeBounds<usize>>(&mut self, range: R) -> IterMutFl<'_, T> {
+        let slots = &mut self.slots[bounds(range)];
+        let filled = count_values(slots);
+        IterMutFl::new(slots, filled)
+    }
+
+    /// Divides the freelist into two mutable views at index `mid`.
+    ///
+    /// The first view covers indices `[0, mid)` and the second `[mid, size)`.  Both views
+    /// use the original indices of the freelist.  See [`FreelistViewMut`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > size`.
+    ///
+    /// # Examples
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([1, 2, 3, 4, 5]);
+    /// let _ = fl.remove(3); // Some(4)
+    ///
+    /// let (mut left, mut right) = fl.split_at_mut(3);
+    /// assert_eq!(left.filled(), 3);
+    /// assert_eq!(right.filled(), 1);
+    ///
+    /// left.iter_mut().for_each(|val| *val += 10);
+    /// assert_eq!(right.get_mut(3), None);
+    /// *right.get_mut(4).unwrap() += 20;
+    ///
+    /// assert_eq!(fl.to_vec(), [11, 12, 13, 25]);
+    /// ```
+    ///
+    /// # Time Complexity
+    ///
+    /// *O*(mid), in order to count the values within the first view.
+    pub fn split_at_mut(&mut self, mid: usize) -> (FreelistViewMut<'_, T>, FreelistViewMut<'_, T>) {
+        let (left, right) = self.slots.split_at_mut(mid);
+        let left_filled = count_values(left);
+        (
+            FreelistViewMut::new(left, 0, left_filled),
+            FreelistViewMut::new(right, mid, self.filled_length - left_filled)
+        )
+    }
+
+    /// Returns an iterator over the full freelist, yielding each value alongside its index.
+    ///
+    /// Unlike `iter().enumerate()`, which only counts the values it has seen, the
+    /// yielded index is the value's actual position in the freelist.
+    ///
+    /// # Examples
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from(['a', 'b', 'c', 'd']);
+    /// let _ = fl.remove(1); // Some('b')
+    /// let mut iterator = fl.iter_indexed();
+    ///
+    /// assert_eq!(iterator.next(), Some((0, &'a')));
+    /// assert_eq!(iterator.next(), Some((2, &'c')));
+    /// assert_eq!(iterator.next_back(), Some((3, &'d')));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    pub fn iter_indexed(&self) -> IterIndexedFl<'_, T> { IterIndexedFl::new(&self.slots, self.filled_length) }
+
+    /// Returns an iterator over the full freelist that allows modifying each value,
+    /// yielding each value alongside its index.
+    ///
+    /// # Examples
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([1, 1, 1, 1]);
+    /// let _ = fl.remove(2); // Some(1)
+    /// for (index, val) in fl.iter_mut_indexed() {
+    ///     *val += index;
+    /// }
+    ///
+    /// assert_eq!(fl.to_vec(), [1, 2, 4]);
+    /// ```
+    pub fn iter_mut_indexed(&mut self) -> IterMutIndexedFl<'_, T> { IterMutIndexedFl::new(&mut self.slots, self.filled_length) }
+
+    /// Converts the freelist into an iterator yielding each value alongside its index.
+    ///
+    /// # Examples
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from(['a', 'b', 'c']);
+    /// let _ = fl.remove(0); // Some('a')
+    ///
+    /// let pairs: Vec<(usize, char)> = fl.into_iter_indexed().collect();
+    /// assert_eq!(pairs, [(1, 'b'), (2, 'c')]);
+    /// ```
+    pub fn into_iter_indexed(self) -> IntoIterIndexedFl<T> { IntoIterIndexedFl::new(self) }
+
+    /// Returns an iterator over the indices of all filled slots.
+    ///
+    /// # Examples
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([1, 2, 4, 8]);
+    /// let _ = fl.remove(0); // Some(1)
+    /// let _ = fl.remove(2); // Some(4)
+    ///
+    /// assert_eq!(fl.keys().collect::<Vec<_>>(), [1, 3]);
+    /// ```
+    pub fn keys(&self) -> KeysFl<'_, T> { KeysFl::new(&self.slots, self.filled_length) }
+
+    /// Returns an adapter that formats the internal layout of the freelist, including
+    /// free slots and the chain linking them, rather than just its values.
+    ///
+    /// # Examples
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([1, 2, 3]);
+    /// let _ = fl.remove(1); // Some(2)
+    ///
+    /// assert_eq!(format!("{fl:?}"), "{0: 1, 2: 3}");
+    /// assert_eq!(
+    ///     format!("{:?}", fl.debug_layout()),
+    ///     "Freelist { slots: [Value(1), Empty, Value(3)], next: Next(1), filled_length: 2 }"
+    /// );
+    /// ```
+    pub fn debug_layout(&self) -> DebugLayout<'_, T> { DebugLayout(self) }
+
+    /// Checks the freelist's internal invariants, returning the first violation found.
+    ///
+    /// A valid freelist records as many filled slots as there are values, and its chain
+    /// of free slots visits every free slot exactly once, ending at the only terminating
+    /// slot, without cycles or links out of range or to filled slots.
+    ///
+    /// A freelist can only be invalid if an `unsafe` method was misused, e.g.
+    /// [`remove_unchecked`](Freelist::remove_unchecked) or
+    /// [`from_raw_parts`](Freelist::from_raw_parts).  With the `debug-invariants` feature
+    /// enabled, this is checked after every call that modifies the freelist's slots.
+    ///
+    /// # Examples
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([1, 2, 3]);
+    /// let _ = fl.remove(1); // Some(2)
+    ///
+    /// assert_eq!(fl.validate(), Ok(()));
+    /// ```
+    ///
+    /// # Time Complexity
+    ///
+    /// *O*(n), where `n` is the size of the freelist. See [`size`](Freelist::size).
+    pub fn validate(&self) -> Result<(), InvariantViolation> {
+        let actual = check_free_chain(&self.slots, &self.next)?;
+        match actual == self.filled_length {
+            true => Ok(()),
+            false => Err(InvariantViolation::FilledMismatch { recorded: self.filled_length, actual })
+        }
+    }
+
+    /// Panics if the freelist is invalid when the `debug-invariants` feature is enabled.
+    #[inline(always)]
+    pub(crate) fn debug_validate(&self) {
+        #[cfg(feature = "debug-invariants")]
+        if let Err(violation) = self.validate() {
+            panic!("freelist invariant violated: {violation}")
+        }
+    }
+
+}
+
+/// Converts any range of indices into bounds that can be used to slice the freelist's slots.
+#[inline]
+fn bounds<R: RangeBounds<usize>>(range: R) -> (Bound<usize>, Bound<usize>) {
+    (range.start_bound().cloned(), range.end_bound().cloned())
+}
+
+/// Builds a freelist through its public operations alone, so that it always upholds its
+/// invariants: every value is pushed, then each value paired with a key is removed in
+/// ascending key order, which determines the order of the chain of free slots.
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+fn fragmented<T, K: Ord>(entries: Vec<(T, Option<K>)>) -> Freelist<T> {
+    let mut removals = Vec::new();
+    let mut fl = Freelist::new();
+    for (value, key) in entries {
+        let index = fl.push(value);
+        if let Some(key) = key { removals.push((key, index)); }
+    }
+    removals.sort_by(|a, b| a.0.cmp(&b.0));
+    for (_, index) in removals { fl.remove(index); }
+    fl
+}
+
+impl<T> Default for Freelist<T> {
+    /// Creates an empty `Freelist<T>`.
+    /// 
+    /// The freelist will not allocate until elements are pushed into it.
+    fn default() -> Self {
+        Self { slots: Vec::new(), next: Slot::Empty, filled_length: 0 }
+    }
+}
+
+impl<T> Index<usize> for Freelist<T> {
+    type Output = T;
+
+    /// Performs the indexing `(container[index])` operation. [Read more](<https://doc.rust-lang.org/std/ops/trait.Index.html#tymethod.index>)
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if the index is out of bounds or is an empty slot.
+    /// Use [`get`](Freelist::get) for a safer alternative.
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        match &self.slots[index] {
+            Slot::Value(element) => element,
+            _ => panic!("attempted to access an empty slot")
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for Freelist<T> {
+
+    /// Performs the mutable indexing `(container[index])` operation. [Read more](<https://doc.rust-lang.org/1.85.1/core/ops/trait.IndexMut.html#tymethod.index_mut>)
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if the index is out of bounds or is an empty slot.
+    /// Use [`get_mut`](Freelist::get_mut) for a safer alternative.
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match &mut self.slots[index] {
+            Slot::Value(element) => element,
             _ => panic!("attempted to access an empty slot")
         }
     }
@@ -605,12 +1252,176 @@ impl<T> FromIterator<T> for Freelist<T> {
 }
 
 
+impl<T> Extend<T> for Freelist<T> {
+    /// Extends the freelist with the contents of an iterator.
+    ///
+    /// Values are pushed in order, filling free slots first.  See [`push`](Freelist::push).
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.slots.reserve(iter.size_hint().0.saturating_sub(self.free()));
+        for value in iter { self.push(value); }
+    }
+}
+
+impl<'a, T: Copy + 'a> Extend<&'a T> for Freelist<T> {
+    /// Extends the freelist with copies of the contents of an iterator.
+    ///
+    /// Values are pushed in order, filling free slots first.  See [`push`](Freelist::push).
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+
+impl<T: Clone> Clone for Freelist<T> {
+    fn clone(&self) -> Self {
+        Self { slots: self.slots.clone(), next: self.next.clone(), filled_length: self.filled_length }
+    }
+
+    /// Overwrites `self` with a clone of `source`, reusing the allocation of `self` where possible.
+    fn clone_from(&mut self, source: &Self) {
+        self.slots.clone_from(&source.slots);
+        self.next.clone_from(&source.next);
+        self.filled_length = source.filled_length;
+        self.debug_validate();
+    }
+}
+
+impl<T: PartialEq> PartialEq for Freelist<T> {
+    /// Two freelists are equal if they hold equal values at the same indices.
+    ///
+    /// The order in which free slots will be reused, as well as any trailing free slots,
+    /// are not taken into account.
+    fn eq(&self, other: &Self) -> bool {
+        self.filled_length == other.filled_length && self.iter_indexed().eq(other.iter_indexed())
+    }
+}
+
+impl<T: Eq> Eq for Freelist<T> {}
+
+impl<T: Hash> Hash for Freelist<T> {
+    /// Hashes each value alongside its index, consistently with [`PartialEq`].
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.filled_length);
+        for (index, value) in self.iter_indexed() {
+            index.hash(state);
+            value.hash(state);
+        }
+    }
+}
+
+impl<T> FromIterator<(usize, T)> for Freelist<T> {
+    /// Constructs a freelist from `(index, value)` pairs.  See [`from_sparse`](Freelist::from_sparse).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the same index appears more than once.
+    fn from_iter<I: IntoIterator<Item = (usize, T)>>(iter: I) -> Self {
+        match Self::from_sparse(iter) {
+            Ok(fl) => fl,
+            Err(err) => panic!("{err}")
+        }
+    }
+}
+
+impl<T> From<BTreeMap<usize, T>> for Freelist<T> {
+    /// Places each value of the map at its key, leaving every other index as a free slot.
+    fn from(map: BTreeMap<usize, T>) -> Self {
+        match Self::from_sparse(map) {
+            Ok(fl) => fl,
+            Err(_) => unreachable!("map keys are unique")
+        }
+    }
+}
+
+impl<T> From<Freelist<T>> for BTreeMap<usize, T> {
+    /// Collects each value of the freelist keyed by its index.
+    fn from(fl: Freelist<T>) -> Self {
+        fl.slots.into_iter()
+            .enumerate()
+            .filter_map(|(index, slot)| Option::from(slot).map(|value| (index, value)))
+            .collect()
+    }
+}
+
+impl<T> From<Vec<Option<T>>> for Freelist<T> {
+    /// Places each `Some` value at its position, leaving each `None` as a free slot.
+    /// Free slots are reused in ascending index order.
+    fn from(data: Vec<Option<T>>) -> Self {
+        let mut fl = Self { filled_length: 0, next: Slot::Empty, slots: Vec::with_capacity(data.len()) };
+        for value in data {
+            match value {
+                Some(value) => { fl.slots.push(Slot::Value(value)); fl.filled_length += 1; },
+                None => fl.slots.push(Slot::Empty),
+            }
+        }
+        fl.sort_free_chain();
+        fl
+    }
+}
+
+impl<T> From<Freelist<T>> for Vec<Option<T>> {
+    /// Converts each slot of the freelist into `Some` value, or `None` if it's free.
+    fn from(fl: Freelist<T>) -> Self {
+        fl.slots.into_iter().map(Option::from).collect()
+    }
+}
+
+
+/// Fixtures shared by the tests of every module.
+#[cfg(test)]
+mod test_util {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::Freelist;
+
+    /// A value that counts how many times values sharing its counter have been dropped.
+    #[derive(Debug)]
+    pub(crate) struct Counted<'a> { pub(crate) drops: &'a Cell<usize>, pub(crate) id: usize }
+
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) { self.drops.set(self.drops.get() + 1) }
+    }
+
+    /// A freelist of `count` counted values, each identified by its initial index.
+    pub(crate) fn counted(drops: &Cell<usize>, count: usize) -> Freelist<Counted<'_>> {
+        Freelist::from_iter((0..count).map(|id| Counted { drops, id }))
+    }
+
+    /// The index and id of each counted value.
+    pub(crate) fn ids(list: &Freelist<Counted>) -> Vec<(usize, usize)> {
+        list.iter_indexed().map(|(index, value)| (index, value.id)).collect()
+    }
+
+    /// A value that panics when dropped if it's set to explode.  Each holds a clone of an
+    /// `Rc`, so that its strong count reveals how many are still alive.
+    pub(crate) struct Bomb { _count: Rc<()>, explode: bool }
+
+    impl Bomb {
+        pub(crate) fn new(count: &Rc<()>, explode: bool) -> Self { Self { _count: count.clone(), explode } }
+    }
+
+    impl Drop for Bomb {
+        fn drop(&mut self) { if self.explode { panic!("boom") } }
+    }
+
+    /// A freelist holding `"b"` and `"c"` at indices 1 and 2, with free slots reused in
+    /// the order 4, 0, 3.
+    pub(crate) fn fragmented() -> Freelist<String> {
+        let mut list = Freelist::from_iter(["a", "b", "c", "d", "e"].map(String::from));
+        for index in [3, 0, 4] { list.remove(index); }
+        list
+    }
+}
 
 #[cfg(test)]
 mod freelist {
+    use std::{cell::Cell, rc::Rc};
+
     use super::{
         Slot::*,
-        Freelist
+        Freelist,
+        test_util::{Bomb, counted, ids}
     };
 
     #[test]
@@ -722,9 +1533,32 @@ mod freelist {
     fn reserve() {
         let mut list = Freelist::<i32>::new();
         list.reserve(16);
+        assert!(list.slots.capacity() >= 16);
+
+        let mut list = Freelist::<i32>::new();
+        list.reserve_exact(16);
         assert_eq!(list.slots.capacity(), 16);
     }
 
+    #[test]
+    fn reserve_counts_free_slots() {
+        let mut list = Freelist::from([0, 1, 2, 3]);
+        list.remove(1);
+        list.remove(2);
+        let capacity = list.capacity();
+
+        list.reserve(1);
+        list.reserve_exact(2);
+        assert_eq!(list.capacity(), capacity);
+        assert_eq!(list.available_capacity(), capacity - 2);
+
+        list.reserve_exact(capacity);
+        assert!(list.available_capacity() >= capacity);
+        let capacity = list.capacity();
+        for value in 0..list.available_capacity() { list.push(value); }
+        assert_eq!(list.capacity(), capacity);
+    }
+
     #[test]
     fn filled() {
         let mut list = Freelist::from([0, 1, 2, 3]);
@@ -778,10 +1612,40 @@ mod freelist {
         assert_eq!(unsafe { list.get_unchecked_mut(2) }, &mut 2);
     }
 
+    #[test]
+    fn get_disjoint_mut() {
+        use crate::GetDisjointError::*;
+
+        let mut list = Freelist::from([0, 1, 2, 3]);
+        list.remove(1);
+
+        let [a, b, c] = list.get_disjoint_mut([3, 0, 2]).unwrap();
+        (*a, *b, *c) = (10, 20, 30);
+        assert_eq!(list.slots, [Value(20), Empty, Value(30), Value(10)]);
+
+        assert_eq!(list.get_disjoint_mut::<0>([]), Ok([]));
+        assert_eq!(list.get_disjoint_mut([0, 4]), Err(IndexOutOfBounds));
+        assert_eq!(list.get_disjoint_mut([0, 1]), Err(VacantSlot));
+        assert_eq!(list.get_disjoint_mut([2, 3, 2]), Err(OverlappingIndices));
+    }
+
+    #[test]
+    fn get_disjoint_unchecked_mut() {
+        let mut list = Freelist::from([0, 1, 2]);
+        let [a, b] = unsafe { list.get_disjoint_unchecked_mut([2, 1]) };
+        std::mem::swap(a, b);
+        assert_eq!(list.slots, [Value(0), Value(2), Value(1)]);
+    }
+
+    #[test]
+    fn get_disjoint_error_display() {
+        assert_eq!(crate::GetDisjointError::VacantSlot.to_string(), "an index refers to a free slot");
+    }
+
     #[test]
     fn iter() {
         let arr = [0, 1, 2, 3];
-        let mut list = Freelist::from(arr.clone());
+        let mut list = Freelist::from(arr);
         list.remove(1);
         let collected = list.iter().copied().collect::<Vec<i32>>();
         assert_eq!([0, 2, 3].as_slice(), collected);
@@ -790,7 +1654,7 @@ mod freelist {
     #[test]
     fn iter_mut() {
         let arr = [0, 1, 2, 3];
-        let mut list = Freelist::from(arr.clone());
+        let mut list = Freelist::from(arr);
         list.remove(1);
         let collected = list.iter_mut().map(|v| *v).collect::<Vec<i32>>();
         assert_eq!([0, 2, 3].as_slice(), collected);
@@ -870,6 +1734,224 @@ mod freelist {
         assert_eq!(iter.next_back(), None);
     }
 
+    #[test]
+    fn iter_indexed() {
+        let mut list = Freelist::from([0, 1, 2, 3]);
+        list.remove(1);
+        let collected = list.iter_indexed().map(|(i, v)| (i, *v)).collect::<Vec<_>>();
+        assert_eq!(collected, [(0, 0), (2, 2), (3, 3)]);
+    }
+
+    #[test]
+    fn iter_mut_indexed() {
+        let mut list = Freelist::from([0, 1, 2, 3]);
+        list.remove(2);
+        for (index, value) in list.iter_mut_indexed() { *value += index * 10 }
+        assert_eq!(list.to_vec(), [0, 11, 33]);
+    }
+
+    #[test]
+    fn into_iter_indexed() {
+        let mut list = Freelist::from([0, 1, 2, 3]);
+        list.remove(0);
+        list.remove(2);
+        assert_eq!(list.into_iter_indexed().rev().collect::<Vec<_>>(), [(3, 3), (1, 1)]);
+    }
+
+    #[test]
+    fn keys() {
+        let mut list = Freelist::from([0, 1, 2, 3]);
+        list.remove(3);
+        list.remove(0);
+        assert_eq!(list.keys().collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn iter_exact_size() {
+        let mut list = Freelist::from([0, 1, 2, 3, 4]);
+        list.remove(1);
+        list.remove(4);
+        assert_eq!(list.iter().len(), 3);
+        assert_eq!(list.iter_mut().len(), 3);
+        assert_eq!(list.iter_indexed().len(), 3);
+        assert_eq!(list.iter_mut_indexed().len(), 3);
+        assert_eq!(list.keys().len(), 3);
+        assert_eq!(list.clone().into_iter_indexed().len(), 3);
+        assert_eq!(list.into_iter().len(), 3);
+    }
+
+    #[test]
+    fn iter_zero_sized() {
+        let mut list = Freelist::from([(), (), (), ()]);
+        list.remove(2);
+
+        let mut iter = list.iter_indexed();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back(), Some((3, &())));
+        assert_eq!(iter.len(), 2);
+        drop(iter);
+        assert_eq!(list.keys().collect::<Vec<_>>(), [0, 1, 3]);
+        assert_eq!(list.into_iter().collect::<Vec<_>>().len(), 3);
+    }
+
+    #[test]
+    fn drain() {
+        let mut list = Freelist::from([0, 1, 2, 3]);
+        list.remove(1);
+        list.remove(3);
+
+        assert_eq!(list.drain().collect::<Vec<_>>(), [(0, 0), (2, 2)]);
+        assert_eq!(list.next, Empty);
+        assert_eq!(list.filled(), 0);
+        assert_eq!(list.slots, vec![]);
+        assert_eq!(list.capacity(), 4);
+
+        assert_eq!(list.push(4), 0);
+    }
+
+    #[test]
+    fn drain_partial() {
+        let mut list = Freelist::from([0, 1, 2, 3]);
+        list.remove(0);
+
+        assert_eq!(list.drain().next_back(), Some((3, 3)));
+        assert_eq!(list.filled(), 0);
+        assert_eq!(list.size(), 0);
+        assert_eq!(list.next_available(), 0);
+    }
+
+    #[test]
+    fn retain() {
+        let mut list = Freelist::from([0, 1, 2, 3, 4, 5]);
+        list.remove(4);
+        list.retain(|index, value| { *value += 1; index % 2 == 0 });
+
+        assert_eq!(list.filled(), 2);
+        assert_eq!(list.slots, [Value(1), Next(4), Value(3), Next(1), Empty, Next(3)]);
+        assert_eq!(list.next, Next(5));
+    }
+
+    #[test]
+    fn retain_panic() {
+        let mut list = Freelist::from([0, 1, 2, 3]);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.retain(|index, _| if index == 2 { panic!("boom") } else { false })
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(list.filled(), 2);
+        assert_eq!(list.slots, [Empty, Next(0), Value(2), Value(3)]);
+        assert_eq!(list.next, Next(1));
+    }
+
+    #[test]
+    fn extract_if() {
+        let mut list = Freelist::from([0, 1, 2, 3]);
+        list.remove(0);
+        let extracted = list.extract_if(|_, value| *value > 1).collect::<Vec<_>>();
+
+        assert_eq!(extracted, [(2, 2), (3, 3)]);
+        assert_eq!(list.filled(), 1);
+        assert_eq!(list.next, Next(3));
+    }
+
+    #[test]
+    fn vacant_indices() {
+        let mut list = Freelist::from([0, 1, 2, 3, 4]);
+        assert_eq!(list.vacant_indices().next(), None);
+
+        list.remove(3);
+        list.remove(0);
+        list.remove(4);
+        assert_eq!(list.vacant_indices().collect::<Vec<_>>(), [4, 0, 3]);
+
+        list.sort_free_chain();
+        assert_eq!(list.vacant_indices().collect::<Vec<_>>(), [0, 3, 4]);
+    }
+
+    #[test]
+    fn free_runs() {
+        let mut list = Freelist::from([0, 1, 2, 3, 4]);
+        assert_eq!(list.free_runs().next(), None);
+
+        list.remove(3);
+        list.remove(0);
+        list.remove(4);
+        assert_eq!(list.free_runs().collect::<Vec<_>>(), [0..1, 3..5]);
+        assert_eq!(list.free_runs().rev().collect::<Vec<_>>(), [3..5, 0..1]);
+    }
+
+    #[test]
+    fn range() {
+        let mut list = Freelist::from([0, 1, 2, 3, 4]);
+        list.remove(1);
+        list.remove(4);
+
+        assert_eq!(list.range(..).copied().collect::<Vec<_>>(), [0, 2, 3]);
+        assert_eq!(list.range(1..=2).copied().collect::<Vec<_>>(), [2]);
+        assert_eq!(list.range(3..).len(), 1);
+        assert_eq!(list.range(5..).next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn range_out_of_bounds() {
+        let list = Freelist::from([0, 1, 2]);
+        let _ = list.range(2..4);
+    }
+
+    #[test]
+    fn range_mut() {
+        let mut list = Freelist::from([0, 1, 2, 3, 4]);
+        list.remove(2);
+        list.range_mut(1..4).for_each(|value| *value *= 10);
+
+        assert_eq!(list.slots, [Value(0), Value(10), Empty, Value(30), Value(4)]);
+    }
+
+    #[test]
+    fn split_at_mut() {
+        let mut list = Freelist::from([0, 1, 2, 3, 4]);
+        list.remove(1);
+
+        let (mut left, mut right) = list.split_at_mut(2);
+        assert_eq!(left.range(), 0..2);
+        assert_eq!(left.filled(), 1);
+        assert_eq!(right.range(), 2..5);
+        assert_eq!(right.filled(), 3);
+
+        *left.get_mut(0).unwrap() = 10;
+        right.iter_mut().for_each(|value| *value += 10);
+        assert_eq!(list.to_vec(), [10, 12, 13, 14]);
+    }
+
+    #[test]
+    fn split_at_mut_edges() {
+        let mut list = Freelist::from([0, 1]);
+
+        let (left, right) = list.split_at_mut(0);
+        assert_eq!((left.size(), right.size()), (0, 2));
+
+        let (left, right) = list.split_at_mut(2);
+        assert_eq!((left.size(), right.size()), (2, 0));
+    }
+
+    #[test]
+    fn cursor_mut() {
+        let mut list = Freelist::from([0, 1, 2, 3]);
+        list.remove(0);
+        let mut cursor = list.cursor_mut();
+
+        assert_eq!(cursor.current(), Some((1, &mut 1)));
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!(cursor.push(5).index(), 1);
+        assert_eq!(cursor.current(), Some((2, &mut 2)));
+
+        assert_eq!(list.filled(), 3);
+        assert_eq!(list.slots, [Empty, Value(5), Value(2), Value(3)]);
+        assert_eq!(list.next, Next(0));
+    }
+
     #[test]
     fn with_capacity() {
         let list = Freelist::<i32>::with_capacity(10);
@@ -896,4 +1978,296 @@ mod freelist {
         assert_eq!(list.size(), 4);
         assert_eq!(list.to_vec(), [1, 7, 3, 5]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn sort_free_chain() {
+        let mut list = Freelist::from([0, 1, 2, 3, 4, 5]);
+        list.remove(4);
+        list.remove(1);
+        list.remove(2);
+
+        list.sort_free_chain();
+
+        assert_eq!(list.next, Next(1));
+        assert_eq!(list.slots, [Value(0), Next(2), Next(4), Value(3), Empty, Value(5)]);
+        assert_eq!(list.filled(), 3);
+
+        for (value, index) in [(6, 1), (7, 2), (8, 4), (9, 6)] {
+            assert_eq!(list.push(value), index);
+        }
+    }
+
+    #[test]
+    fn sort_free_chain_no_free() {
+        let mut list = Freelist::from([0, 1, 2]);
+        list.sort_free_chain();
+
+        assert_eq!(list.next, Empty);
+        assert_eq!(list.slots, [Value(0), Value(1), Value(2)]);
+    }
+
+    #[test]
+    fn eq() {
+        let mut a = Freelist::from([0, 1, 2, 3]);
+        let mut b = Freelist::from([0, 1, 2]);
+        a.remove(3);
+        assert_eq!(a, b);
+
+        // Free chain order is ignored.
+        a.remove(0); a.remove(1);
+        b.remove(1); b.remove(0);
+        assert_ne!(a.next, b.next);
+        assert_eq!(a, b);
+
+        b.push(5);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn hash() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let state = RandomState::new();
+        let mut a = Freelist::from([0, 1, 2]);
+        let mut b = Freelist::from([0, 1]);
+        a.remove(2);
+        assert_eq!(state.hash_one(&a), state.hash_one(&b));
+
+        b.remove(0);
+        a.remove(1);
+        assert_ne!(state.hash_one(&a), state.hash_one(&b));
+    }
+
+    #[test]
+    fn extend() {
+        let mut list = Freelist::from([0, 1, 2]);
+        list.remove(0);
+        list.extend([3, 4]);
+        assert_eq!(list.slots, [Value(3), Value(1), Value(2), Value(4)]);
+
+        list.extend(&[5, 6]);
+        assert_eq!(list.to_vec(), [3, 1, 2, 4, 5, 6]);
+    }
+
+    #[test]
+    fn clone_from() {
+        let mut source = Freelist::from([0, 1, 2]);
+        source.remove(1);
+
+        let mut list = Freelist::from_iter(0..10);
+        let capacity = list.capacity();
+        list.clone_from(&source);
+
+        assert_eq!(list.capacity(), capacity);
+        assert_eq!(list.slots, source.slots);
+        assert_eq!(list.next, source.next);
+        assert_eq!(list.filled(), 2);
+    }
+
+    #[test]
+    fn from_sparse() {
+        let list = Freelist::from_sparse([(4, 4), (1, 1), (2, 2)]).unwrap();
+        assert_eq!(list.slots, [Next(3), Value(1), Value(2), Empty, Value(4)]);
+        assert_eq!(list.next, Next(0));
+        assert_eq!(list.filled(), 3);
+
+        let list = Freelist::<i32>::from_sparse([]).unwrap();
+        assert_eq!(list.size(), 0);
+        assert_eq!(list.next, Empty);
+
+        let err = Freelist::from_sparse([(1, 1), (0, 0), (1, 2)]).unwrap_err();
+        assert_eq!(err.index(), 1);
+        assert_eq!(err.to_string(), "index 1 appeared more than once");
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of range for a freelist")]
+    fn from_sparse_max_index() {
+        let _ = Freelist::from_sparse([(usize::MAX, ())]);
+    }
+
+    #[test]
+    fn from_iter_indexed() {
+        let list: Freelist<char> = [(2, 'c'), (0, 'a')].into_iter().collect();
+        assert_eq!(list.slots, [Value('a'), Empty, Value('c')]);
+        assert_eq!(list.next, Next(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "index 0 appeared more than once")]
+    fn from_iter_indexed_duplicate() {
+        let _: Freelist<char> = [(0, 'a'), (0, 'b')].into_iter().collect();
+    }
+
+    #[test]
+    fn btree_map() {
+        use std::collections::BTreeMap;
+
+        let map = BTreeMap::from([(1, String::from("b")), (3, String::from("d"))]);
+        let mut list = Freelist::from(map.clone());
+        assert_eq!(list.size(), 4);
+        assert_eq!(list.get(3).map(String::as_str), Some("d"));
+        assert_eq!(BTreeMap::from(list.clone()), map);
+
+        list.remove(1);
+        assert_eq!(BTreeMap::from(list).into_keys().collect::<Vec<_>>(), [3]);
+    }
+
+    #[test]
+    fn vec_option() {
+        let data = vec![None, Some(String::from("b")), None, Some(String::from("d"))];
+        let mut list = Freelist::from(data.clone());
+        assert_eq!(list.filled(), 2);
+        assert_eq!(list.next, Next(0));
+        assert_eq!(Vec::from(list.clone()), data);
+
+        assert_eq!(list.push(String::from("a")), 0);
+        assert_eq!(list.push(String::from("c")), 2);
+        assert_eq!(list.push(String::from("e")), 4);
+    }
+
+    #[test]
+    fn compactify_drops_once() {
+        let drops = Cell::new(0);
+        let mut list = counted(&drops, 7);
+        for index in [1, 2, 5] { list.remove(index); }
+        assert_eq!(drops.get(), 3);
+
+        list.compactify();
+        assert_eq!(drops.get(), 3);
+        assert_eq!(ids(&list), [(0, 0), (1, 6), (2, 4), (3, 3)]);
+
+        drop(list);
+        assert_eq!(drops.get(), 7);
+    }
+
+    #[test]
+    fn compactify_strings() {
+        let mut list = Freelist::from_iter(["a", "b", "c", "d", "e"].map(String::from));
+        list.remove(0);
+        list.remove(2);
+        list.compactify();
+
+        assert_eq!(list.slots, [Value("e".into()), Value("b".into()), Value("d".into())]);
+        assert_eq!(list.push("f".into()), 3);
+    }
+
+    #[test]
+    fn retain_drops_once() {
+        let drops = Cell::new(0);
+        let mut list = counted(&drops, 6);
+        list.remove(0);
+        list.retain(|_, value| value.id % 2 == 0);
+        assert_eq!(drops.get(), 4);
+        assert_eq!(ids(&list), [(2, 2), (4, 4)]);
+
+        drop(list);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn extract_if_drops_once() {
+        let drops = Cell::new(0);
+        let mut list = counted(&drops, 6);
+        drop(list.extract_if(|index, _| index < 4).next());
+        assert_eq!(drops.get(), 1);
+
+        let extracted = list.extract_if(|index, _| index % 2 == 1).collect::<Vec<_>>();
+        assert_eq!(drops.get(), 1);
+        drop(extracted);
+        assert_eq!(drops.get(), 4);
+
+        drop(list);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn cursor_drops_once() {
+        let drops = Cell::new(0);
+        let mut list = counted(&drops, 4);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        drop(cursor.remove_current());
+        drop(cursor.remove_current());
+        assert_eq!(drops.get(), 2);
+        assert_eq!(ids(&list), [(0, 0), (3, 3)]);
+
+        drop(list);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn to_vec_drops_once() {
+        let drops = Cell::new(0);
+        let mut list = counted(&drops, 4);
+        list.remove(1);
+
+        let values = list.to_vec();
+        assert_eq!(drops.get(), 1);
+        assert_eq!(values.iter().map(|value| value.id).collect::<Vec<_>>(), [0, 2, 3]);
+        drop(values);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn clear_drops_once() {
+        let drops = Cell::new(0);
+        let mut list = counted(&drops, 4);
+        list.remove(2);
+        list.clear();
+        assert_eq!(drops.get(), 4);
+
+        drop(list);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn clear_panicking_drop() {
+        let rc = Rc::new(());
+        let mut list = Freelist::from([false, true, false].map(|explode| Bomb::new(&rc, explode)));
+        list.remove(0);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| list.clear()));
+
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+        assert_eq!((list.size(), list.filled()), (0, 0));
+        assert_eq!(list.push(Bomb::new(&rc, false)), 0);
+    }
+
+    #[test]
+    fn validate() {
+        use super::InvariantViolation::*;
+
+        let mut list = Freelist::from([0, 1, 2, 3]);
+        assert_eq!(list.validate(), Ok(()));
+        list.remove(2);
+        list.remove(0);
+        assert_eq!(list.validate(), Ok(()));
+
+        let invalid = |slots: Vec<_>, next, filled_length| Freelist::<i32> { slots, next, filled_length }.validate();
+        assert_eq!(invalid(vec![Value(0), Empty], Next(1), 2), Err(FilledMismatch { recorded: 2, actual: 1 }));
+        assert_eq!(invalid(vec![Value(0)], Value(1), 1), Err(ValueAtHead));
+        assert_eq!(invalid(vec![Value(0), Next(4)], Next(1), 1), Err(LinkOutOfBounds(4)));
+        assert_eq!(invalid(vec![Value(0), Next(0)], Next(1), 1), Err(LinkToValue(0)));
+        assert_eq!(invalid(vec![Next(1), Next(0)], Next(0), 0), Err(Cycle(0)));
+        assert_eq!(invalid(vec![Empty, Value(1), Empty], Next(2), 1), Err(Unlinked(0)));
+        assert_eq!(invalid(vec![Value(0), Empty], Empty, 1), Err(Unlinked(1)));
+    }
+
+    #[test]
+    fn invariant_violation_display() {
+        let err = Freelist::<i32> { slots: vec![Next(1), Next(0)], next: Next(0), filled_length: 0 }
+            .validate()
+            .unwrap_err();
+        assert_eq!(err.to_string(), "the free chain is cyclic, revisiting index 0");
+    }
+
+    #[test]
+    #[cfg(feature = "debug-invariants")]
+    #[should_panic(expected = "freelist invariant violated")]
+    fn debug_invariants() {
+        let mut parts = Freelist::from([0, 1]).into_raw_parts();
+        parts.filled = 1;
+        let _ = unsafe { Freelist::from_raw_parts(parts) };
+    }
+}
@@ -0,0 +1,345 @@
+//! Parallel iteration over a [`Freelist`] using [`rayon`](https://docs.rs/rayon).
+//!
+//! The freelist's slots are split into producers as any other slice would be, with
+//! each producer skipping over freed slots.  `Freelist<T>`, `&Freelist<T>` and
+//! `&mut Freelist<T>` implement [`IntoParallelIterator`], so freelists work with
+//! [`ParallelExtend`] and any code generic over rayon's traits.
+
+use rayon::{
+    iter::{FilterMap, plumbing::UnindexedConsumer},
+    prelude::*,
+    slice, vec,
+};
+
+use crate::{Freelist, Slot};
+
+/// Filters slots of type `S` down to their values of type `V`.
+type Values<I, S, V> = FilterMap<I, fn(S) -> Option<V>>;
+
+/// Parallel iterator over the values of a [`Freelist`], see [`Freelist::par_iter`].
+pub struct ParIterFl<'a, T: Sync>(Values<slice::Iter<'a, Slot<T>>, &'a Slot<T>, &'a T>);
+
+/// Parallel iterator over mutable values of a [`Freelist`], see [`Freelist::par_iter_mut`].
+pub struct ParIterMutFl<'a, T: Send>(Values<slice::IterMut<'a, Slot<T>>, &'a mut Slot<T>, &'a mut T>);
+
+/// Owning parallel iterator over the values of a [`Freelist`], see [`Freelist::into_par_iter`].
+pub struct IntoParIterFl<T: Send>(Values<vec::IntoIter<Slot<T>>, Slot<T>, T>);
+
+impl<'a, T: Sync + 'a> ParallelIterator for ParIterFl<'a, T> {
+    type Item = &'a T;
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        self.0.drive_unindexed(consumer)
+    }
+}
+
+impl<'a, T: Send + 'a> ParallelIterator for ParIterMutFl<'a, T> {
+    type Item = &'a mut T;
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        self.0.drive_unindexed(consumer)
+    }
+}
+
+impl<T: Send> ParallelIterator for IntoParIterFl<T> {
+    type Item = T;
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        self.0.drive_unindexed(consumer)
+    }
+}
+
+impl<'a, T: Sync + 'a> IntoParallelIterator for &'a Freelist<T> {
+    type Iter = ParIterFl<'a, T>;
+    type Item = &'a T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIterFl(self.slots.par_iter().filter_map(Option::from))
+    }
+}
+
+impl<'a, T: Send + 'a> IntoParallelIterator for &'a mut Freelist<T> {
+    type Iter = ParIterMutFl<'a, T>;
+    type Item = &'a mut T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIterMutFl(self.slots.par_iter_mut().filter_map(Option::from))
+    }
+}
+
+impl<T: Send> IntoParallelIterator for Freelist<T> {
+    type Iter = IntoParIterFl<T>;
+    type Item = T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        IntoParIterFl(self.slots.into_par_iter().filter_map(Option::from))
+    }
+}
+
+impl<T> Freelist<T> {
+
+    /// Returns a parallel iterator over the full freelist.
+    ///
+    /// The iterator will skip over freed slots.
+    ///
+    /// # Examples
+    /// ```
+    /// use fffl::Freelist;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut fl = Freelist::from([1, 2, 4, 8]);
+    /// let _ = fl.remove(2); // Some(4)
+    ///
+    /// assert_eq!(fl.par_iter().sum::<i32>(), 11);
+    /// ```
+    pub fn par_iter(&self) -> ParIterFl<'_, T>
+    where
+        T: Sync
+    {
+        self.into_par_iter()
+    }
+
+    /// Returns a parallel iterator over the full freelist that allows modifying each value.
+    ///
+    /// # Examples
+    /// ```
+    /// use fffl::Freelist;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut fl = Freelist::from([1, 3, 5, 7]);
+    /// let _ = fl.remove(2); // Some(5)
+    /// fl.par_iter_mut().for_each(|val| *val += 1);
+    ///
+    /// assert_eq!(fl.to_vec(), [2, 4, 8]);
+    /// ```
+    pub fn par_iter_mut(&mut self) -> ParIterMutFl<'_, T>
+    where
+        T: Send
+    {
+        self.into_par_iter()
+    }
+
+    /// Converts the freelist into a parallel iterator.
+    ///
+    /// # Examples
+    /// ```
+    /// use fffl::Freelist;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut fl = Freelist::from([1, 2, 3]);
+    /// let _ = fl.remove(0); // Some(1)
+    ///
+    /// assert_eq!(fl.into_par_iter().collect::<Vec<_>>(), [2, 3]);
+    /// ```
+    pub fn into_par_iter(self) -> IntoParIterFl<T>
+    where
+        T: Send
+    {
+        IntoParallelIterator::into_par_iter(self)
+    }
+
+    /// Returns a parallel iterator over the full freelist, yielding each value alongside its index.
+    ///
+    /// # Examples
+    /// ```
+    /// use fffl::Freelist;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut fl = Freelist::from(['a', 'b', 'c']);
+    /// let _ = fl.remove(1); // Some('b')
+    ///
+    /// assert_eq!(fl.par_iter_indexed().collect::<Vec<_>>(), [(0, &'a'), (2, &'c')]);
+    /// ```
+    pub fn par_iter_indexed(&self) -> impl ParallelIterator<Item = (usize, &T)>
+    where
+        T: Sync
+    {
+        self.slots.par_iter()
+            .enumerate()
+            .filter_map(|(index, slot)| Option::from(slot).map(|value| (index, value)))
+    }
+
+    /// Returns a parallel iterator over the full freelist that allows modifying each value,
+    /// yielding each value alongside its index.
+    ///
+    /// # Examples
+    /// ```
+    /// use fffl::Freelist;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut fl = Freelist::from([0, 0, 0]);
+    /// fl.par_iter_mut_indexed().for_each(|(index, val)| *val = index);
+    ///
+    /// assert_eq!(fl.to_vec(), [0, 1, 2]);
+    /// ```
+    pub fn par_iter_mut_indexed(&mut self) -> impl ParallelIterator<Item = (usize, &mut T)>
+    where
+        T: Send
+    {
+        self.slots.par_iter_mut()
+            .enumerate()
+            .filter_map(|(index, slot)| Option::from(slot).map(|value| (index, value)))
+    }
+
+    /// Converts the freelist into a parallel iterator yielding each value alongside its index.
+    ///
+    /// # Examples
+    /// ```
+    /// use fffl::Freelist;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut fl = Freelist::from(['a', 'b', 'c']);
+    /// let _ = fl.remove(0); // Some('a')
+    ///
+    /// assert_eq!(fl.into_par_iter_indexed().collect::<Vec<_>>(), [(1, 'b'), (2, 'c')]);
+    /// ```
+    pub fn into_par_iter_indexed(self) -> impl ParallelIterator<Item = (usize, T)>
+    where
+        T: Send
+    {
+        self.slots.into_par_iter()
+            .enumerate()
+            .filter_map(|(index, slot)| Option::from(slot).map(|value| (index, value)))
+    }
+
+    /// Retains only the values specified by the predicate, evaluating the predicate in parallel.
+    ///
+    /// Behaves exactly as [`retain`](Freelist::retain), including the order in which freed
+    /// slots are added to the chain of free slots, except that the predicate may be called
+    /// in any order.  The values themselves are removed and dropped on the calling thread
+    /// once every predicate has been evaluated, so should the predicate panic no values
+    /// are removed.
+    ///
+    /// # Examples
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([1, 2, 3, 4, 5]);
+    /// fl.par_retain(|_, v| *v % 2 == 1);
+    ///
+    /// assert_eq!(fl.to_vec(), [1, 3, 5]);
+    /// ```
+    pub fn par_retain<F>(&mut self, f: F)
+    where
+        T: Send,
+        F: Fn(usize, &mut T) -> bool + Sync + Send
+    {
+        let removed: Vec<usize> = self.slots.par_iter_mut()
+            .enumerate()
+            .filter_map(|(index, slot)| match slot {
+                Slot::Value(value) => (!f(index, value)).then_some(index),
+                _ => None
+            })
+            .collect();
+
+        for index in removed {
+            drop(unsafe { self.remove_unchecked(index) });
+        }
+    }
+}
+
+impl<T: Send> ParallelExtend<T> for Freelist<T> {
+    /// Extends the freelist with the contents of a parallel iterator.
+    ///
+    /// Values are collected in parallel and then pushed in order, filling
+    /// free slots first.  See [`push`](Freelist::push).
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = T>
+    {
+        let values: Vec<T> = par_iter.into_par_iter().collect();
+        for value in values { self.push(value); }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use rayon::prelude::*;
+
+    use crate::{Freelist, Slot::*};
+
+    fn sparse() -> Freelist<usize> {
+        let mut list = Freelist::from_iter(0..1000);
+        for index in (0..1000).step_by(3) { list.remove(index); }
+        list
+    }
+
+    #[test]
+    fn par_iter() {
+        let list = sparse();
+        let expected = list.iter().collect::<Vec<_>>();
+        assert_eq!(list.par_iter().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn par_iter_mut() {
+        let mut list = sparse();
+        list.par_iter_mut().for_each(|value| *value *= 2);
+        assert!(list.iter().all(|value| value % 2 == 0));
+        assert_eq!(list.filled(), 666);
+    }
+
+    #[test]
+    fn into_par_iter() {
+        let list = sparse();
+        let expected = list.clone().to_vec();
+        assert_eq!(list.into_par_iter().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn par_iter_indexed() {
+        let mut list = sparse();
+        assert!(list.par_iter_indexed().all(|(index, value)| index == *value));
+        assert!(list.par_iter_mut_indexed().all(|(index, value)| index == *value));
+        assert_eq!(list.into_par_iter_indexed().filter(|(index, _)| index % 3 == 0).count(), 0);
+    }
+
+    #[test]
+    fn par_retain() {
+        let mut list = Freelist::from([0, 1, 2, 3, 4, 5]);
+        list.remove(4);
+        list.par_retain(|index, value| { *value += 1; index % 2 == 0 });
+
+        assert_eq!(list.filled(), 2);
+        assert_eq!(list.slots, [Value(1), Next(4), Value(3), Next(1), Empty, Next(3)]);
+        assert_eq!(list.next, Next(5));
+    }
+
+    #[test]
+    fn par_retain_panic() {
+        let mut list = sparse();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.par_retain(|index, _| if index == 500 { panic!("boom") } else { false })
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(list.filled(), 666);
+    }
+
+    #[test]
+    fn into_parallel_iterator() {
+        fn sum<I: IntoParallelIterator<Item = usize>>(iter: I) -> usize { iter.into_par_iter().sum() }
+
+        let mut list = sparse();
+        let expected = list.iter().sum::<usize>();
+        assert_eq!(sum((&list).into_par_iter().copied()), expected);
+        assert_eq!(ParallelIterator::count(IntoParallelRefIterator::par_iter(&list)), 666);
+
+        IntoParallelRefMutIterator::par_iter_mut(&mut list).for_each(|value| *value += 1);
+        assert_eq!(sum(list), expected + 666);
+
+        let mut other = Freelist::new();
+        other.par_extend(sparse());
+        assert_eq!(other.filled(), 666);
+    }
+
+    #[test]
+    fn par_extend() {
+        let mut list = Freelist::from([0, 1, 2]);
+        list.remove(1);
+        list.par_extend(vec![3, 4]);
+
+        assert_eq!(list.slots, [Value(0), Value(3), Value(2), Value(4)]);
+    }
+}
@@ -0,0 +1,90 @@
+//! [Proptest](https://docs.rs/proptest) strategies for generating fragmented freelists.
+//!
+//! Generated freelists are built by pushing every value and then removing some of them
+//! in a random order, so they always uphold the freelist's invariants while exercising
+//! free slots and varied orders of reuse.  Removed values shrink towards being kept.
+//!
+//! # Examples
+//!
+//! ```
+//! use fffl::Freelist;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     // Mark each function with `#[test]` within a test suite.
+//!     fn push_fills_free_slots(mut fl in fffl::proptest::freelist(any::<u8>(), 0..64)) {
+//!         let next = fl.next_available();
+//!         prop_assert_eq!(fl.push(0), next);
+//!     }
+//!
+//!     fn any_freelist(fl in any::<Freelist<i32>>()) {
+//!         prop_assert_eq!(fl.iter().count(), fl.filled());
+//!     }
+//! }
+//! # push_fills_free_slots();
+//! # any_freelist();
+//! ```
+
+use ::proptest::{
+    arbitrary::{Arbitrary, StrategyFor, any},
+    collection::{SizeRange, VecStrategy, vec},
+    option::{OptionStrategy, of},
+    strategy::{Map, Strategy},
+};
+
+use crate::{Freelist, fragmented};
+
+type Entries<S> = VecStrategy<(S, OptionStrategy<StrategyFor<u32>>)>;
+
+/// Creates a strategy generating freelists of `size` slots, some of them free, with values
+/// generated by `element`.
+pub fn freelist<S: Strategy>(element: S, size: impl Into<SizeRange>) -> impl Strategy<Value = Freelist<S::Value>> {
+    entries(element, size.into()).prop_map(fragmented)
+}
+
+fn entries<S: Strategy>(element: S, size: SizeRange) -> Entries<S> {
+    vec((element, of(any::<u32>())), size)
+}
+
+impl<T: Arbitrary> Arbitrary for Freelist<T> {
+    type Parameters = (SizeRange, T::Parameters);
+    type Strategy = Map<Entries<T::Strategy>, fn(Vec<(T, Option<u32>)>) -> Self>;
+
+    fn arbitrary_with((size, params): Self::Parameters) -> Self::Strategy {
+        entries(T::arbitrary_with(params), size).prop_map(fragmented)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use ::proptest::{prelude::*, strategy::ValueTree, test_runner::TestRunner};
+
+    use super::*;
+
+    #[test]
+    fn generates_free_slots() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = freelist(any::<u8>(), 1..32);
+
+        let mut fragmented_count = 0;
+        for _ in 0..64 {
+            let fl = strategy.new_tree(&mut runner).unwrap().current();
+            assert!((1..32).contains(&fl.size()));
+            assert_eq!(fl.vacant_indices().count(), fl.free());
+            if fl.free() > 0 { fragmented_count += 1; }
+        }
+        assert!(fragmented_count > 0);
+    }
+
+    proptest! {
+        #[test]
+        fn upholds_invariants(mut fl in any::<Freelist<u8>>()) {
+            prop_assert_eq!(fl.iter().count(), fl.filled());
+            let free = fl.vacant_indices().collect::<Vec<_>>();
+            prop_assert_eq!(free.len(), fl.free());
+            for index in free { prop_assert_eq!(fl.push(0), index); }
+            prop_assert_eq!(fl.push(0), fl.size() - 1);
+        }
+    }
+}
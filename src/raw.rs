@@ -0,0 +1,170 @@
+//! Access to the storage of a [`Freelist`].
+//!
+//! A freelist's storage is a single contiguous buffer of slots, each of which is either
+//! a value, a link to the next free slot, or the terminator of the chain of free slots.
+//! The head of the chain is kept outside of the buffer.  A freelist may be taken apart
+//! into its [`RawParts`] with [`Freelist::into_raw_parts`] and put back together with
+//! [`Freelist::from_raw_parts`], e.g. to hand its buffer to other code.
+//!
+//! # Layout
+//!
+//! Without the `repr-c` feature the layout of a slot is unspecified.  Enabling it gives
+//! each slot a `#[repr(C, u8)]` layout: a `u8` tag followed by a union of the variants'
+//! fields.  This layout is part of the crate's stable API, and changing it is a breaking
+//! change.  The header `include/fffl_slot.h` describes it, declaring the slot type for a
+//! given `T` with a C layout:
+//!
+//! ```c
+//! #include "fffl_slot.h"
+//!
+//! FFFL_SLOT(fffl_slot_u32, uint32_t);
+//!
+//! const fffl_slot_u32 *slots = (const fffl_slot_u32 *)parts.ptr;
+//! for (size_t i = 0; i < parts.length; i++) {
+//!     if (slots[i].tag == FFFL_SLOT_VALUE) { /* slots[i].data.value */ }
+//! }
+//! ```
+//!
+//! A slot tagged `FFFL_SLOT_VALUE` (`0`) holds a value, while free slots form a chain
+//! starting at [`RawParts::next`]: one tagged `FFFL_SLOT_NEXT` (`1`) holds the index of the
+//! next free slot, and the last is tagged `FFFL_SLOT_EMPTY` (`2`).  [`RawParts`] itself is
+//! always `#[repr(C)]`.
+//!
+//! There is no view of the buffer as a slice of `Option<T>`, as a free slot needs room for
+//! the index of the next free slot where `None` has none.  Use [`Freelist::get`] or
+//! [`Freelist::iter_indexed`] instead.
+
+use std::{marker::PhantomData, mem::ManuallyDrop};
+
+use crate::{Freelist, Slot};
+
+/// The raw components of a [`Freelist`].
+///
+/// Created by [`Freelist::into_raw_parts`] and consumed by [`Freelist::from_raw_parts`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct RawParts<T> {
+    /// Pointer to the buffer of slots.  See the [module documentation](self) for its layout.
+    pub ptr: *mut u8,
+    /// Number of initialized slots in the buffer.
+    pub length: usize,
+    /// Number of slots the buffer has room for.
+    pub capacity: usize,
+    /// Index of the head of the chain of free slots, or `length` if there are no free slots.
+    pub next: usize,
+    /// Number of slots holding a value.
+    pub filled: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Freelist<T> {
+
+    /// Decomposes the freelist into its raw components.
+    ///
+    /// The caller becomes responsible for the memory previously managed by the freelist.
+    /// The only way to release it is to convert the parts back into a freelist with
+    /// [`from_raw_parts`](Freelist::from_raw_parts).
+    ///
+    /// # Examples
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([1, 2, 3]);
+    /// let _ = fl.remove(1); // Some(2)
+    ///
+    /// let parts = fl.into_raw_parts();
+    /// assert_eq!((parts.length, parts.next, parts.filled), (3, 1, 2));
+    ///
+    /// let fl = unsafe { Freelist::from_raw_parts(parts) };
+    /// assert_eq!(fl.to_vec(), [1, 3]);
+    /// ```
+    pub fn into_raw_parts(self) -> RawParts<T> {
+        let next = self.next_available();
+        let Freelist { slots, filled_length, .. } = self;
+        let mut slots = ManuallyDrop::new(slots);
+        RawParts {
+            ptr: slots.as_mut_ptr().cast(),
+            length: slots.len(),
+            capacity: slots.capacity(),
+            next,
+            filled: filled_length,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a freelist directly from its raw components.
+    ///
+    /// # Safety
+    ///
+    /// `parts` must have been returned by [`into_raw_parts`](Freelist::into_raw_parts)
+    /// for a `Freelist<T>` of the same `T`, and must not have been used to create a
+    /// freelist since.  The values in the buffer may have been modified in place, but
+    /// which slots hold values and the chain of free slots must be left as they were.
+    pub unsafe fn from_raw_parts(parts: RawParts<T>) -> Self {
+        let RawParts { ptr, length, capacity, next, filled, .. } = parts;
+        let fl = Self {
+            slots: unsafe { Vec::from_raw_parts(ptr.cast::<Slot<T>>(), length, capacity) },
+            next: if next < length { Slot::Next(next) } else { Slot::Empty },
+            filled_length: filled,
+        };
+        fl.debug_validate();
+        fl
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_parts_round_trip() {
+        let mut fl = Freelist::from_iter(["a", "b", "c", "d"].map(String::from));
+        fl.remove(3);
+        fl.remove(0);
+
+        let parts = fl.into_raw_parts();
+        assert_eq!((parts.length, parts.next, parts.filled), (4, 0, 2));
+
+        let mut fl = unsafe { Freelist::from_raw_parts(parts) };
+        assert_eq!(fl.push(String::from("e")), 0);
+        assert_eq!(fl.push(String::from("f")), 3);
+        assert_eq!(fl.push(String::from("g")), 4);
+    }
+
+    #[test]
+    fn raw_parts_no_free() {
+        let parts = Freelist::from([1, 2]).into_raw_parts();
+        assert_eq!(parts.next, 2);
+
+        let mut fl = unsafe { Freelist::from_raw_parts(parts) };
+        assert_eq!(fl.next, Slot::Empty);
+        assert_eq!(fl.push(3), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "repr-c")]
+    fn repr_c_layout() {
+        use std::mem::{offset_of, size_of};
+
+        assert_eq!(size_of::<Slot<u64>>(), 16);
+        assert_eq!(size_of::<Slot<u8>>(), 2 * size_of::<usize>());
+
+        let slots = [Slot::Value(7u32), Slot::Next(1), Slot::Empty];
+        let tags = slots.each_ref().map(|slot| unsafe { *(slot as *const Slot<u32>).cast::<u8>() });
+        assert_eq!(tags, [0, 1, 2]);
+
+        // Mirrors `FFFL_SLOT` in include/fffl_slot.h.
+        #[repr(C)]
+        union Data<T: Copy> { value: T, next: usize }
+        #[repr(C)]
+        struct CSlot<T: Copy> { tag: u8, data: Data<T> }
+
+        assert_eq!(size_of::<Slot<u32>>(), size_of::<CSlot<u32>>());
+        assert_eq!(size_of::<Slot<[u8; 24]>>(), size_of::<CSlot<[u8; 24]>>());
+        let c_slots = slots.each_ref().map(|slot| unsafe { &*(slot as *const Slot<u32>).cast::<CSlot<u32>>() });
+        assert_eq!(unsafe { (c_slots[0].data.value, c_slots[1].data.next) }, (7, 1));
+        assert_eq!(offset_of!(CSlot<u64>, data), 8);
+        assert_eq!(offset_of!(RawParts<u32>, next), 3 * size_of::<usize>());
+    }
+}
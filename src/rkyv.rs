@@ -0,0 +1,181 @@
+//! Zero-copy archiving of a [`Freelist`] with [`rkyv`](https://docs.rs/rkyv).
+//!
+//! A `Freelist<T>` archives as an [`ArchivedFreelist<T>`] holding every slot, so indices
+//! and the order in which free slots will be reused are preserved.  Values can be read
+//! directly from the archived bytes, and deserializing validates the chain of free slots
+//! just as deserializing with serde does.
+//!
+//! Accessing an archive with validation checks that its recorded number of filled slots
+//! matches its slots, so the counts reported by an `ArchivedFreelist` can be trusted.
+
+use ::rkyv::{Archive, Deserialize, bytecheck::Verify, rancor::{Fallible, Source}};
+
+use crate::{ArchivedFreelist, Freelist, InvariantViolation, Slot, slot::{ArchivedSlot, check_free_chain}};
+
+impl<T: Archive> ArchivedFreelist<T> {
+
+    /// Returns the number of filled slots.
+    #[inline]
+    pub fn filled(&self) -> usize { self.filled_length.to_native() as usize }
+
+    /// Returns the number of free slots.
+    #[inline]
+    pub fn free(&self) -> usize { self.size() - self.filled() }
+
+    /// Returns the number of slots, including free slots.
+    #[inline]
+    pub fn size(&self) -> usize { self.slots.len() }
+
+    /// Returns the index the next value pushed would be placed at,
+    /// once deserialized.  See [`Freelist::next_available`].
+    #[inline]
+    pub fn next_available(&self) -> usize {
+        match &self.next {
+            ArchivedSlot::Next(index) => index.to_native() as usize,
+            _ => self.size()
+        }
+    }
+
+    /// Returns a reference to the archived value at the given index,
+    /// or `None` if the index is a free slot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T::Archived> {
+        match &self.slots[index] {
+            ArchivedSlot::Value(value) => Some(value),
+            _ => None
+        }
+    }
+
+    /// Returns an iterator over the archived values, skipping free slots.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T::Archived> {
+        self.iter_indexed().map(|(_, value)| value)
+    }
+
+    /// Returns an iterator over the archived values, yielding each alongside its index.
+    ///
+    /// # Examples
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([1u32, 2, 3]);
+    /// let _ = fl.remove(1); // Some(2)
+    ///
+    /// let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&fl).unwrap();
+    /// let archived = rkyv::access::<fffl::ArchivedFreelist<u32>, rkyv::rancor::Error>(&bytes).unwrap();
+    ///
+    /// let values = archived.iter_indexed().map(|(index, value)| (index, value.to_native()));
+    /// assert_eq!(values.collect::<Vec<_>>(), [(0, 1), (2, 3)]);
+    /// assert_eq!(archived.filled(), 2);
+    /// ```
+    pub fn iter_indexed(&self) -> impl DoubleEndedIterator<Item = (usize, &T::Archived)> {
+        self.slots.iter()
+            .enumerate()
+            .filter_map(|(index, slot)| match slot {
+                ArchivedSlot::Value(value) => Some((index, value)),
+                _ => None
+            })
+    }
+}
+
+// SAFETY: `verify` only succeeds if the recorded number of filled slots is the number of
+// archived slots holding a value, which is all `ArchivedFreelist` relies on.
+unsafe impl<T, C> Verify<C> for ArchivedFreelist<T>
+where
+    T: Archive,
+    C: Fallible + ?Sized,
+    C::Error: Source
+{
+    fn verify(&self, _context: &mut C) -> Result<(), C::Error> {
+        let actual = self.slots.iter().filter(|slot| matches!(slot, ArchivedSlot::Value(_))).count();
+        match self.filled() {
+            recorded if recorded == actual => Ok(()),
+            recorded => Err(C::Error::new(InvariantViolation::FilledMismatch { recorded, actual })),
+        }
+    }
+}
+
+impl<T, D> Deserialize<Freelist<T>, D> for ArchivedFreelist<T>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+    D::Error: Source
+{
+    /// Deserializes the freelist, failing if its chain of free slots is invalid.
+    fn deserialize(&self, deserializer: &mut D) -> Result<Freelist<T>, D::Error> {
+        let slots: Vec<Slot<T>> = self.slots.deserialize(deserializer)?;
+        let next: Slot<T> = self.next.deserialize(deserializer)?;
+        let filled_length = check_free_chain(&slots, &next).map_err(D::Error::new)?;
+        Ok(Freelist { slots, next, filled_length })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use ::rkyv::{access, deserialize, rancor, to_bytes, util::AlignedVec};
+
+    use super::*;
+    use crate::test_util::fragmented;
+
+    fn archive(list: &Freelist<String>) -> AlignedVec {
+        to_bytes::<rancor::Error>(list).unwrap()
+    }
+
+    #[test]
+    fn access_archived() {
+        let bytes = archive(&fragmented());
+        let archived = access::<ArchivedFreelist<String>, rancor::Error>(&bytes).unwrap();
+
+        assert_eq!((archived.filled(), archived.free(), archived.size()), (2, 3, 5));
+        assert_eq!(archived.next_available(), 4);
+        assert_eq!(archived.get(1).map(|value| value.as_str()), Some("b"));
+        assert_eq!(archived.get(3), None);
+        assert_eq!(archived.iter().rev().map(|value| value.as_str()).collect::<Vec<_>>(), ["c", "b"]);
+        assert_eq!(archived.iter_indexed().map(|(index, _)| index).collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut list = fragmented();
+        let bytes = archive(&list);
+        let archived = access::<ArchivedFreelist<String>, rancor::Error>(&bytes).unwrap();
+        let mut restored = deserialize::<Freelist<String>, rancor::Error>(archived).unwrap();
+
+        assert_eq!(restored.slots, list.slots);
+        assert_eq!(restored.next, list.next);
+        assert_eq!(restored.filled(), 2);
+        for value in ["f", "g", "h", "i"].map(String::from) {
+            assert_eq!(restored.push(value.clone()), list.push(value));
+        }
+    }
+
+    #[test]
+    fn reject_invalid_chain() {
+        let cyclic = Freelist::<String> {
+            slots: vec![Slot::Next(1), Slot::Next(0)],
+            next: Slot::Next(0),
+            filled_length: 0,
+        };
+        let bytes = archive(&cyclic);
+        let archived = access::<ArchivedFreelist<String>, rancor::Error>(&bytes).unwrap();
+        let err = deserialize::<Freelist<String>, rancor::Error>(archived).unwrap_err();
+        assert!(err.to_string().contains("cyclic"), "{err}");
+    }
+
+    #[test]
+    fn reject_filled_mismatch() {
+        let corrupt = Freelist::<String> {
+            slots: vec![Slot::Value(String::from("a")), Slot::Empty],
+            next: Slot::Next(1),
+            filled_length: 3,
+        };
+        let bytes = archive(&corrupt);
+        let err = access::<ArchivedFreelist<String>, rancor::Error>(&bytes).err().unwrap();
+        assert!(err.to_string().contains("records 3 filled slots but holds 1"), "{err}");
+    }
+}
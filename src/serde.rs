@@ -0,0 +1,134 @@
+//! [Serde](https://serde.rs) support for [`Freelist`].
+//!
+//! A `Freelist<T>` is serialized faithfully as a struct containing every slot,
+//! free or filled, alongside the head of the chain of free slots:
+//!
+//! ```text
+//! Freelist {
+//!     slots: [Value(T) | Next(usize) | Empty, ...],
+//!     next: Next(usize) | Empty,
+//! }
+//! ```
+//!
+//! This preserves the index of every value as well as the exact order in which free
+//! slots will be reused, so that after deserializing [`push`](Freelist::push) returns
+//! the same index it would have before serializing.
+//!
+//! Deserializing validates the chain of free slots, rejecting input in which it is
+//! cyclic, points out of range or at a filled slot, or fails to visit every free slot.
+//!
+//! # Examples
+//!
+//! ```
+//! use fffl::Freelist;
+//!
+//! let mut fl = Freelist::from([1, 2, 3, 4]);
+//! let _ = fl.remove(1);
+//! let _ = fl.remove(3);
+//!
+//! let json = serde_json::to_string(&fl).unwrap();
+//! let mut restored: Freelist<i32> = serde_json::from_str(&json).unwrap();
+//!
+//! assert_eq!(restored.push(5), fl.push(5));
+//! assert_eq!(restored.to_vec(), fl.to_vec());
+//! ```
+//!
+//! For mostly empty freelists, the more compact [`sparse`] format stores only the
+//! filled slots and may be selected per field with `#[serde(with = "fffl::serde::sparse")]`.
+
+pub mod sparse;
+
+use ::serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Freelist, Slot, slot::check_free_chain};
+
+impl<T: Serialize> Serialize for Freelist<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Freelist", 2)?;
+        state.serialize_field("slots", &self.slots)?;
+        state.serialize_field("next", &self.next)?;
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Freelist")]
+struct RawFreelist<T> {
+    slots: Vec<Slot<T>>,
+    next: Slot<T>,
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Freelist<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let RawFreelist { slots, next } = RawFreelist::deserialize(deserializer)?;
+        let filled_length = check_free_chain(&slots, &next).map_err(de::Error::custom)?;
+        Ok(Freelist { slots, next, filled_length })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::fragmented;
+
+    #[test]
+    fn serialize() {
+        let json = serde_json::to_string(&fragmented()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"slots":[{"Next":3},{"Value":"b"},{"Value":"c"},"Empty",{"Next":0}],"next":{"Next":4}}"#
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut list = fragmented();
+        let mut restored: Freelist<String> =
+            serde_json::from_str(&serde_json::to_string(&list).unwrap()).unwrap();
+
+        assert_eq!(restored.slots, list.slots);
+        assert_eq!(restored.next, list.next);
+        assert_eq!(restored.filled(), 2);
+        for value in ["f", "g", "h", "i"].map(String::from) {
+            assert_eq!(restored.push(value.clone()), list.push(value));
+        }
+    }
+
+    #[test]
+    fn round_trip_empty() {
+        let json = serde_json::to_string(&Freelist::<i32>::new()).unwrap();
+        let restored: Freelist<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.size(), 0);
+        assert_eq!(restored.next, Slot::Empty);
+    }
+
+    fn reject(json: &str, msg: &str) {
+        let err = serde_json::from_str::<Freelist<i32>>(json).unwrap_err();
+        assert!(err.to_string().contains(msg), "{err}");
+    }
+
+    #[test]
+    fn reject_out_of_range() {
+        reject(r#"{"slots":[{"Value":1},"Empty"],"next":{"Next":2}}"#, "out of range");
+        reject(r#"{"slots":[{"Next":5},"Empty"],"next":{"Next":0}}"#, "out of range");
+    }
+
+    #[test]
+    fn reject_cyclic() {
+        reject(r#"{"slots":[{"Next":1},{"Next":0}],"next":{"Next":0}}"#, "cyclic");
+        reject(r#"{"slots":[{"Next":0}],"next":{"Next":0}}"#, "cyclic");
+    }
+
+    #[test]
+    fn reject_filled() {
+        reject(r#"{"slots":[{"Value":1}],"next":{"Next":0}}"#, "filled slot");
+        reject(r#"{"slots":[{"Value":1}],"next":{"Value":1}}"#, "head of the free chain");
+    }
+
+    #[test]
+    fn reject_unvisited() {
+        reject(r#"{"slots":[{"Value":1},"Empty"],"next":"Empty"}"#, "every free slot");
+        reject(r#"{"slots":["Empty","Empty"],"next":{"Next":0}}"#, "every free slot");
+    }
+}
@@ -0,0 +1,288 @@
+//! A compact serialization format for sparse freelists.
+//!
+//! Rather than every slot, only the size of the freelist, its values paired with their
+//! indices and (optionally) the order in which free slots will be reused are stored:
+//!
+//! ```text
+//! Freelist {
+//!     size: usize,
+//!     values: [(usize, T), ...],
+//!     free: Option<[usize, ...]>,
+//! }
+//! ```
+//!
+//! Use it with serde's `with` attribute.  [`serialize`] stores the order of the free
+//! slots, whereas [`unordered::serialize`] omits it.  When no order was stored, the
+//! chain of free slots is rebuilt in ascending index order when deserializing (see
+//! [`Freelist::sort_free_chain`]).
+//!
+//! Deserializing allocates every slot up to the stored `size`, which is not bounded by the
+//! length of the data.  Limit the size of untrusted input before deserializing it.
+//!
+//! # Examples
+//!
+//! ```
+//! use fffl::Freelist;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct World {
+//!     #[serde(with = "fffl::serde::sparse")]
+//!     entities: Freelist<char>,
+//! }
+//!
+//! let mut entities = Freelist::from(['a', 'b', 'c', 'd']);
+//! let _ = entities.remove(0);
+//! let _ = entities.remove(2);
+//!
+//! let json = serde_json::to_string(&World { entities }).unwrap();
+//! assert_eq!(json, r#"{"entities":{"size":4,"values":[[1,"b"],[3,"d"]],"free":[2,0]}}"#);
+//!
+//! let mut world: World = serde_json::from_str(&json).unwrap();
+//! assert_eq!(world.entities.push('e'), 2);
+//! ```
+
+use std::mem::replace;
+
+use ::serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Freelist, Slot};
+
+/// Serializes a freelist in the sparse format, including the order of its free slots.
+pub fn serialize<T, S>(freelist: &Freelist<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer
+{
+    serialize_sparse(freelist, serializer, true)
+}
+
+/// Deserializes a freelist from the sparse format.
+///
+/// If the order of the free slots was stored it is restored exactly, otherwise free
+/// slots will be reused in ascending index order.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Freelist<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>
+{
+    let RawSparse { size, values, free } = RawSparse::deserialize(deserializer)?;
+    from_sparse_parts(size, values, free).map_err(de::Error::custom)
+}
+
+/// The sparse format without the order of free slots.
+///
+/// # Examples
+///
+/// ```
+/// use fffl::Freelist;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct World {
+///     #[serde(with = "fffl::serde::sparse::unordered")]
+///     entities: Freelist<char>,
+/// }
+///
+/// let mut entities = Freelist::from(['a', 'b', 'c', 'd']);
+/// let _ = entities.remove(0);
+/// let _ = entities.remove(2);
+///
+/// let json = serde_json::to_string(&World { entities }).unwrap();
+/// assert_eq!(json, r#"{"entities":{"size":4,"values":[[1,"b"],[3,"d"]],"free":null}}"#);
+///
+/// let mut world: World = serde_json::from_str(&json).unwrap();
+/// assert_eq!(world.entities.push('e'), 0);
+/// ```
+pub mod unordered {
+    use super::*;
+
+    /// Serializes a freelist in the sparse format, omitting the order of its free slots.
+    pub fn serialize<T, S>(freelist: &Freelist<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer
+    {
+        serialize_sparse(freelist, serializer, false)
+    }
+
+    pub use super::deserialize;
+}
+
+struct Values<'a, T>(&'a Freelist<T>);
+
+impl<T: Serialize> Serialize for Values<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter_indexed())
+    }
+}
+
+struct FreeOrder<'a, T>(&'a Freelist<T>);
+
+impl<T> Serialize for FreeOrder<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.vacant_indices())
+    }
+}
+
+fn serialize_sparse<T, S>(freelist: &Freelist<T>, serializer: S, ordered: bool) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer
+{
+    let mut state = serializer.serialize_struct("Freelist", 3)?;
+    state.serialize_field("size", &freelist.size())?;
+    state.serialize_field("values", &Values(freelist))?;
+    state.serialize_field("free", &ordered.then_some(FreeOrder(freelist)))?;
+    state.end()
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Freelist")]
+struct RawSparse<T> {
+    size: usize,
+    values: Vec<(usize, T)>,
+    #[serde(default)]
+    free: Option<Vec<usize>>,
+}
+
+/// Rebuilds a freelist from its size, its indexed values and optionally the order of its free slots.
+fn from_sparse_parts<T>(
+    size: usize,
+    values: Vec<(usize, T)>,
+    free: Option<Vec<usize>>
+) -> Result<Freelist<T>, &'static str> {
+    if let Some(order) = &free {
+        match values.len().checked_add(order.len()) {
+            Some(listed) if listed < size => return Err("the free order does not list every free slot"),
+            Some(listed) if listed == size => (),
+            _ => return Err("the free order lists more slots than are free"),
+        }
+    }
+
+    let mut slots = Vec::new();
+    slots.try_reserve_exact(size).map_err(|_| "the size of the freelist is too large")?;
+    slots.resize_with(size, || Slot::Empty);
+    let filled_length = values.len();
+    for (index, value) in values {
+        match slots.get_mut(index) {
+            None => return Err("a value's index is out of range"),
+            Some(Slot::Value(_)) => return Err("a value's index is duplicated"),
+            Some(slot) => *slot = Slot::Value(value),
+        }
+    }
+
+    let mut freelist = Freelist { slots, next: Slot::Empty, filled_length };
+    match free {
+        None => freelist.sort_free_chain(),
+        Some(order) => {
+            let mut listed = vec![false; size];
+            for &index in &order {
+                match freelist.slots.get(index) {
+                    None => return Err("a free index is out of range"),
+                    Some(Slot::Value(_)) => return Err("a free index refers to a filled slot"),
+                    Some(_) if listed[index] => return Err("a free index is duplicated"),
+                    Some(_) => listed[index] = true,
+                }
+            }
+            // Threading the chain back to front leaves `order[0]` at its head.
+            for &index in order.iter().rev() {
+                freelist.slots[index] = replace(&mut freelist.next, Slot::Next(index));
+            }
+        }
+    }
+
+    Ok(freelist)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::fragmented;
+
+    #[derive(Serialize, Deserialize)]
+    struct Ordered(#[serde(with = "super")] Freelist<String>);
+
+    #[derive(Serialize, Deserialize)]
+    struct Unordered(#[serde(with = "super::unordered")] Freelist<String>);
+
+    #[test]
+    fn serialize() {
+        assert_eq!(
+            serde_json::to_string(&Ordered(fragmented())).unwrap(),
+            r#"{"size":5,"values":[[1,"b"],[2,"c"]],"free":[4,0,3]}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Unordered(fragmented())).unwrap(),
+            r#"{"size":5,"values":[[1,"b"],[2,"c"]],"free":null}"#
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let list = fragmented();
+        let json = serde_json::to_string(&Ordered(list.clone())).unwrap();
+        let Ordered(restored) = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.slots, list.slots);
+        assert_eq!(restored.next, list.next);
+        assert_eq!(restored.filled(), 2);
+    }
+
+    #[test]
+    fn round_trip_unordered() {
+        let json = serde_json::to_string(&Unordered(fragmented())).unwrap();
+        let Unordered(mut restored) = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.filled(), 2);
+        assert_eq!(restored.size(), 5);
+        assert_eq!(restored.get(4), None);
+        assert_eq!(restored.iter().map(String::as_str).collect::<Vec<_>>(), ["b", "c"]);
+        assert_eq!(["f", "g", "h", "i"].map(|value| restored.push(value.into())), [0, 3, 4, 5]);
+    }
+
+    #[test]
+    fn missing_free_order() {
+        let Ordered(mut restored) = serde_json::from_str(r#"{"size":4,"values":[[1,"b"]]}"#).unwrap();
+        assert_eq!(restored.size(), 4);
+        assert_eq!(restored.free(), 3);
+        assert_eq!(restored.push(String::from("a")), 0);
+        assert_eq!(restored.push(String::from("c")), 2);
+        assert_eq!(restored.push(String::from("d")), 3);
+    }
+
+    #[test]
+    fn round_trip_empty() {
+        let json = serde_json::to_string(&Ordered(Freelist::new())).unwrap();
+        let Ordered(restored) = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.size(), 0);
+        assert_eq!(restored.next, Slot::Empty);
+    }
+
+    fn reject(json: &str, msg: &str) {
+        let err = serde_json::from_str::<Ordered>(json).err().unwrap();
+        assert!(err.to_string().contains(msg), "{err}");
+    }
+
+    #[test]
+    fn reject_values() {
+        reject(r#"{"size":2,"values":[[2,"b"]],"free":null}"#, "out of range");
+        reject(r#"{"size":2,"values":[[0,"b"],[0,"c"]],"free":null}"#, "duplicated");
+    }
+
+    #[test]
+    fn reject_free_order() {
+        reject(r#"{"size":2,"values":[[0,"b"]],"free":[]}"#, "every free slot");
+        reject(r#"{"size":1,"values":[[0,"b"]],"free":[1]}"#, "more slots than are free");
+        reject(r#"{"size":2,"values":[[0,"b"]],"free":[2]}"#, "out of range");
+        reject(r#"{"size":2,"values":[[0,"b"]],"free":[0]}"#, "filled slot");
+        reject(r#"{"size":3,"values":[[0,"b"]],"free":[1,1]}"#, "duplicated");
+    }
+
+    #[test]
+    fn oversized() {
+        reject(r#"{"size":1000000000000000,"values":[],"free":[]}"#, "every free slot");
+        reject(r#"{"size":1000000000000000,"values":[[999999999999999,"b"]],"free":[]}"#, "every free slot");
+        reject(r#"{"size":1000000000000000000,"values":[[1,"b"]],"free":null}"#, "too large");
+    }
+}
@@ -1,10 +1,14 @@
 use std::hint::unreachable_unchecked;
 
+use crate::InvariantViolation;
 
 
-#[derive(PartialEq, Debug, Clone)]
 
 /// Container struct for items in Freelist
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "repr-c", repr(C, u8))]
 pub(super) enum Slot<T> {
     /// Contains a value `T`
     Value(T),
@@ -20,7 +24,7 @@ impl <T>Slot<T> {
     /// Converts [`Slot::Value(T)`](Slot::Value) into [`Some(T)`](Option::Some)
     /// 
     /// Calling on any other variant is UB.
-    pub(super) unsafe fn to_some_unchecked(self) -> Option<T> {
+    pub(super) unsafe fn into_some_unchecked(self) -> Option<T> {
         match self {
             Slot::Value(value) => Some(value),
             // Because this is only used internally, we can ensure we don't end up here.
@@ -41,7 +45,7 @@ impl <T>Slot<T> {
     }
 
     #[inline(always)]
-    pub(super) unsafe fn to_value_unchecked(self) -> T {
+    pub(super) unsafe fn into_value_unchecked(self) -> T {
         match self {
             Slot::Value(value) => value,
             _ => unsafe { unreachable_unchecked() }
@@ -62,7 +66,38 @@ impl <T>Slot<T> {
 
     #[inline]
     pub(super) const fn is_value(&self) -> bool {
-        if let Slot::Value(_) = self { true } else { false }
+        matches!(self, Slot::Value(_))
+    }
+}
+
+/// Counts the slots containing a value.
+#[inline]
+pub(super) fn count_values<T>(slots: &[Slot<T>]) -> usize {
+    slots.iter().filter(|slot| slot.is_value()).count()
+}
+
+/// Walks the chain of free slots starting at `next`, returning the number of
+/// filled slots if the chain visits every free slot exactly once.
+pub(super) fn check_free_chain<T>(slots: &[Slot<T>], next: &Slot<T>) -> Result<usize, InvariantViolation> {
+    let mut visited = vec![false; slots.len()];
+    let mut link = next;
+
+    loop {
+        let index = match link {
+            Slot::Next(index) => *index,
+            Slot::Empty => break,
+            Slot::Value(_) => return Err(InvariantViolation::ValueAtHead),
+        };
+        if index >= slots.len() { return Err(InvariantViolation::LinkOutOfBounds(index)) }
+        if visited[index] { return Err(InvariantViolation::Cycle(index)) }
+        visited[index] = true;
+        link = &slots[index];
+        if link.is_value() { return Err(InvariantViolation::LinkToValue(index)) }
+    }
+
+    match slots.iter().zip(visited).position(|(slot, visited)| !slot.is_value() && !visited) {
+        Some(index) => Err(InvariantViolation::Unlinked(index)),
+        None => Ok(count_values(slots))
     }
 }
 
@@ -105,14 +140,14 @@ impl <'a, T: 'a> From<&'a mut Slot<T>> for Option<&'a mut T> {
 
 
 #[cfg(test)]
-mod slot {
+mod tests {
 
     use super::Slot;
 
     #[test]
-    fn to_some_unchecked() {
+    fn into_some_unchecked() {
         let slot = Slot::Value(5);
-        assert_eq!(unsafe { slot.to_some_unchecked() }, Some(5));
+        assert_eq!(unsafe { slot.into_some_unchecked() }, Some(5));
     }
 
     #[test]
@@ -127,6 +162,13 @@ mod slot {
         assert_eq!(unsafe { slot.as_value_unchecked_mut() }, &mut 5);
     }
 
+    #[test]
+    fn count_values() {
+        let slots = [Slot::Value(0), Slot::Next(3), Slot::Value(2), Slot::Empty];
+        assert_eq!(super::count_values(&slots), 2);
+        assert_eq!(super::count_values::<i32>(&[]), 0);
+    }
+
     #[test]
     fn from_t() {
         let slot = Slot::from(5i32);
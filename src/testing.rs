@@ -0,0 +1,243 @@
+//! A model-based harness for differential testing of [`Freelist`].
+//!
+//! [`check`] applies a sequence of [`Op`]s to both a freelist and a reference model, a
+//! `BTreeMap<usize, T>` of values alongside a stack of free indices, and compares the two
+//! after every step.  Paired with the [`ops`] strategy, proptest generates random
+//! sequences and shrinks any failure to a minimal one.
+//!
+//! # Examples
+//!
+//! ```
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     // Mark each function with `#[test]` within a test suite.
+//!     fn matches_model(ops in fffl::testing::ops(any::<u16>(), 0..128)) {
+//!         fffl::testing::check(&ops)?;
+//!     }
+//! }
+//! # matches_model();
+//! ```
+
+use std::{collections::BTreeMap, fmt::Debug};
+
+use ::proptest::{
+    collection::{SizeRange, vec},
+    prelude::*,
+    test_runner::TestCaseError,
+};
+
+use crate::Freelist;
+
+/// An operation applied to both the freelist and the reference model by [`check`].
+///
+/// Indices are reduced to the current state when applied, so any sequence of operations
+/// is valid and shrinks independently of the state it runs against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Op<T> {
+    /// Pushes a value.
+    Push(T),
+    /// Removes the slot at the given index modulo the size, which may be free.
+    /// Does nothing while the freelist is empty.
+    Remove(usize),
+    /// Removes the value at the given position, modulo the number of values, with
+    /// [`Freelist::remove_unchecked`].  Does nothing while there are no values.
+    RemoveUnchecked(usize),
+    /// Calls [`Freelist::compactify`].
+    Compactify,
+    /// Calls [`Freelist::clear`].
+    Clear,
+    /// Calls [`Freelist::reserve`].
+    Reserve(usize),
+    /// Calls [`Freelist::reserve_exact`].
+    ReserveExact(usize),
+    /// Drains every value with [`Freelist::drain`].
+    Drain,
+    /// Overwrites every value through [`Freelist::iter_mut`].
+    Overwrite(T),
+}
+
+/// Creates a strategy generating `len` operations, with values generated by `element`.
+///
+/// Pushes and removals are weighted above operations that reset the freelist, so the
+/// generated freelists are usually fragmented.
+pub fn ops<S>(element: S, len: impl Into<SizeRange>) -> impl Strategy<Value = Vec<Op<S::Value>>>
+where
+    S: Strategy + Clone,
+    S::Value: Clone,
+{
+    let op = prop_oneof![
+        8 => element.clone().prop_map(Op::Push),
+        4 => any::<usize>().prop_map(Op::Remove),
+        2 => any::<usize>().prop_map(Op::RemoveUnchecked),
+        1 => Just(Op::Compactify),
+        1 => Just(Op::Clear),
+        1 => (0..64usize).prop_map(Op::Reserve),
+        1 => (0..64usize).prop_map(Op::ReserveExact),
+        1 => Just(Op::Drain),
+        1 => element.prop_map(Op::Overwrite),
+    ];
+    vec(op, len)
+}
+
+/// Applies `ops` in order to a new freelist and the reference model, failing on the first
+/// step after which their values, free slots or iterators differ.
+pub fn check<T: Clone + Debug + PartialEq>(ops: &[Op<T>]) -> Result<(), TestCaseError> {
+    let mut fl = Freelist::new();
+    let mut model = Model::default();
+
+    for (step, op) in ops.iter().enumerate() {
+        apply(&mut fl, &mut model, op)
+            .and_then(|()| compare(&fl, &model))
+            .map_err(|err| TestCaseError::fail(format!("step {step}, {op:?}: {err}")))?;
+    }
+    Ok(())
+}
+
+/// The reference model: values by index, and the free indices in the order they were
+/// freed, so the last is reused first.
+struct Model<T> {
+    values: BTreeMap<usize, T>,
+    free: Vec<usize>,
+    size: usize,
+}
+
+impl<T> Default for Model<T> {
+    fn default() -> Self { Self { values: BTreeMap::new(), free: Vec::new(), size: 0 } }
+}
+
+impl<T> Model<T> {
+    fn push(&mut self, value: T) -> usize {
+        let index = self.free.pop().unwrap_or_else(|| {
+            self.size += 1;
+            self.size - 1
+        });
+        self.values.insert(index, value);
+        index
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        let removed = self.values.remove(&index);
+        if removed.is_some() { self.free.push(index) }
+        removed
+    }
+
+    /// Moves the last values into the first free slots until none are left behind a value.
+    fn compactify(&mut self) {
+        let holes = (0..self.size).filter(|index| !self.values.contains_key(index)).collect::<Vec<_>>();
+        let plugs = self.values.keys().rev().copied().collect::<Vec<_>>();
+        for (hole, plug) in holes.into_iter().zip(plugs) {
+            if hole > plug { break }
+            let value = self.values.remove(&plug).unwrap();
+            self.values.insert(hole, value);
+        }
+        self.free.clear();
+        self.size = self.values.len();
+    }
+
+    fn clear(&mut self) {
+        self.values.clear();
+        self.free.clear();
+        self.size = 0;
+    }
+}
+
+fn apply<T: Clone + Debug + PartialEq>(
+    fl: &mut Freelist<T>,
+    model: &mut Model<T>,
+    op: &Op<T>
+) -> Result<(), TestCaseError> {
+    match op {
+        Op::Push(value) => prop_assert_eq!(fl.push(value.clone()), model.push(value.clone())),
+        Op::Remove(index) => if model.size > 0 {
+            let index = index % model.size;
+            prop_assert_eq!(fl.remove(index), model.remove(index));
+        }
+        Op::RemoveUnchecked(position) => if !model.values.is_empty() {
+            let index = *model.values.keys().nth(position % model.values.len()).unwrap();
+            // SAFETY: `index` holds a value in the model, which matched the freelist.
+            prop_assert_eq!(Some(unsafe { fl.remove_unchecked(index) }), model.remove(index));
+        }
+        Op::Compactify => {
+            fl.compactify();
+            model.compactify();
+        }
+        Op::Clear => {
+            fl.clear();
+            model.clear();
+        }
+        Op::Reserve(additional) => {
+            fl.reserve(*additional);
+            prop_assert!(fl.available_capacity() >= *additional);
+        }
+        Op::ReserveExact(additional) => {
+            fl.reserve_exact(*additional);
+            prop_assert!(fl.available_capacity() >= *additional);
+        }
+        Op::Drain => {
+            let drained = fl.drain().collect::<Vec<_>>();
+            prop_assert_eq!(drained, std::mem::take(&mut model.values).into_iter().collect::<Vec<_>>());
+            model.clear();
+        }
+        Op::Overwrite(value) => {
+            fl.iter_mut().for_each(|slot| *slot = value.clone());
+            model.values.values_mut().for_each(|slot| *slot = value.clone());
+        }
+    }
+    Ok(())
+}
+
+fn compare<T: Clone + Debug + PartialEq>(fl: &Freelist<T>, model: &Model<T>) -> Result<(), TestCaseError> {
+    prop_assert_eq!(fl.validate(), Ok(()));
+    prop_assert_eq!(fl.filled(), model.values.len());
+    prop_assert_eq!(fl.size(), model.size);
+    prop_assert_eq!(fl.free(), model.free.len());
+    prop_assert_eq!(fl.available_capacity(), fl.capacity() - model.values.len());
+    prop_assert_eq!(fl.next_available(), model.free.last().copied().unwrap_or(model.size));
+
+    for index in 0..model.size {
+        prop_assert_eq!(fl.get(index), model.values.get(&index));
+    }
+
+    let values = model.values.values().collect::<Vec<_>>();
+    prop_assert_eq!(fl.iter().len(), values.len());
+    prop_assert_eq!(fl.iter().collect::<Vec<_>>(), values.clone());
+    prop_assert_eq!(fl.iter().rev().collect::<Vec<_>>(), values.into_iter().rev().collect::<Vec<_>>());
+    prop_assert_eq!(fl.keys().collect::<Vec<_>>(), model.values.keys().copied().collect::<Vec<_>>());
+    prop_assert_eq!(
+        fl.iter_indexed().collect::<Vec<_>>(),
+        model.values.iter().map(|(&index, value)| (index, value)).collect::<Vec<_>>()
+    );
+    prop_assert_eq!(fl.vacant_indices().collect::<Vec<_>>(), model.free.iter().rev().copied().collect::<Vec<_>>());
+    prop_assert_eq!(
+        fl.clone().into_iter_indexed().collect::<Vec<_>>(),
+        model.values.clone().into_iter().collect::<Vec<_>>()
+    );
+    prop_assert_eq!(fl.clone().into_iter().collect::<Vec<_>>(), model.values.values().cloned().collect::<Vec<_>>());
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use ::proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn sequence() {
+        let ops = [
+            Op::Push('a'), Op::Push('b'), Op::Push('c'), Op::Push('d'),
+            Op::Remove(1), Op::Remove(5), Op::RemoveUnchecked(0), Op::Push('e'),
+            Op::Reserve(0), Op::ReserveExact(3), Op::Compactify, Op::Overwrite('f'), Op::Drain, Op::Push('g'), Op::Clear,
+        ];
+        check(&ops).unwrap();
+    }
+
+    proptest! {
+        #[test]
+        fn matches_model(ops in ops(any::<u8>(), 0..256)) {
+            check(&ops)?;
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use std::ops::Range;
+
+use crate::{Slot, iterators::*};
+
+/// A mutable view into a contiguous range of a [`Freelist`](crate::Freelist)'s slots.
+///
+/// Views are created by [`Freelist::split_at_mut`](crate::Freelist::split_at_mut) and allow
+/// disjoint ranges of the same freelist to be modified independently, e.g. across threads.
+/// All indices used and returned by a view are those of the original freelist.
+///
+/// A view cannot push or remove values, as doing so would require access to the freelist's
+/// chain of free slots.
+#[derive(Debug)]
+pub struct FreelistViewMut<'a, T> {
+    slots: &'a mut [Slot<T>],
+    offset: usize,
+    filled_length: usize,
+}
+
+impl<'a, T> FreelistViewMut<'a, T> {
+
+    #[inline]
+    pub(crate) fn new(slots: &'a mut [Slot<T>], offset: usize, filled_length: usize) -> Self {
+        Self { slots, offset, filled_length }
+    }
+
+    /// Returns the range of freelist indices covered by this view.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([1, 2, 3, 4, 5]);
+    /// let (left, right) = fl.split_at_mut(2);
+    ///
+    /// assert_eq!(left.range(), 0..2);
+    /// assert_eq!(right.range(), 2..5);
+    /// ```
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.slots.len()
+    }
+
+    /// Returns the number of filled slots within the view.
+    #[inline]
+    pub fn filled(&self) -> usize { self.filled_length }
+
+    /// Returns the number of slots within the view, including freed slots.
+    #[inline]
+    pub fn size(&self) -> usize { self.slots.len() }
+
+    /// Returns a reference to the element at the given freelist index,
+    /// or `None` if the index is a free slot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is outside of the view's [`range`](FreelistViewMut::range).
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        (&self.slots[self.local(index)]).into()
+    }
+
+    /// Returns a mutable reference to the element at the given freelist index,
+    /// or `None` if the index is a free slot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is outside of the view's [`range`](FreelistViewMut::range).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([1, 2, 3, 4]);
+    /// let (mut left, mut right) = fl.split_at_mut(2);
+    ///
+    /// *left.get_mut(1).unwrap() += 10;
+    /// *right.get_mut(3).unwrap() += 10;
+    ///
+    /// assert_eq!(fl.to_vec(), [1, 12, 3, 14]);
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let local = self.local(index);
+        (&mut self.slots[local]).into()
+    }
+
+    /// Returns an iterator over the values within the view.
+    pub fn iter(&self) -> IterFl<'_, T> { IterFl::new(self.slots, self.filled_length) }
+
+    /// Returns an iterator over the values within the view that allows modifying each value.
+    pub fn iter_mut(&mut self) -> IterMutFl<'_, T> { IterMutFl::new(self.slots, self.filled_length) }
+
+    /// Returns an iterator over the values within the view, yielding each value
+    /// alongside its freelist index.
+    pub fn iter_indexed(&self) -> IterIndexedFl<'_, T> {
+        IterIndexedFl::with_offset(self.slots, self.filled_length, self.offset)
+    }
+
+    /// Returns an iterator over the values within the view that allows modifying each value,
+    /// yielding each value alongside its freelist index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fffl::Freelist;
+    ///
+    /// let mut fl = Freelist::from([0, 0, 0, 0]);
+    /// let _ = fl.remove(1);
+    /// let (_, mut right) = fl.split_at_mut(1);
+    ///
+    /// for (index, value) in right.iter_mut_indexed() {
+    ///     *value = index;
+    /// }
+    ///
+    /// assert_eq!(fl.to_vec(), [0, 2, 3]);
+    /// ```
+    pub fn iter_mut_indexed(&mut self) -> IterMutIndexedFl<'_, T> {
+        IterMutIndexedFl::with_offset(self.slots, self.filled_length, self.offset)
+    }
+
+    #[inline]
+    fn local(&self, index: usize) -> usize {
+        match index.checked_sub(self.offset) {
+            Some(local) if local < self.slots.len() => local,
+            _ => panic!("index {index} is outside of the view's range {:?}", self.range())
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slots() -> [Slot<i32>; 4] {
+        [Slot::Value(0), Slot::Empty, Slot::Value(2), Slot::Value(3)]
+    }
+
+    #[test]
+    fn get() {
+        let mut slots = slots();
+        let view = FreelistViewMut::new(&mut slots[1..], 1, 2);
+
+        assert_eq!(view.range(), 1..4);
+        assert_eq!(view.get(1), None);
+        assert_eq!(view.get(2), Some(&2));
+    }
+
+    #[test]
+    fn get_mut() {
+        let mut slots = slots();
+        let mut view = FreelistViewMut::new(&mut slots[1..], 1, 2);
+
+        *view.get_mut(3).unwrap() = 13;
+        assert_eq!(view.get_mut(1), None);
+        assert_eq!(slots[3], Slot::Value(13));
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_below_range() {
+        let mut slots = slots();
+        let view = FreelistViewMut::new(&mut slots[1..], 1, 2);
+        view.get(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_above_range() {
+        let mut slots = slots();
+        let view = FreelistViewMut::new(&mut slots[..2], 0, 1);
+        view.get(2);
+    }
+
+    #[test]
+    fn iter_indexed() {
+        let mut slots = slots();
+        let mut view = FreelistViewMut::new(&mut slots[1..], 1, 2);
+
+        assert_eq!(view.iter().len(), 2);
+        assert_eq!(view.iter_indexed().collect::<Vec<_>>(), [(2, &2), (3, &3)]);
+        assert_eq!(view.iter_mut_indexed().next_back(), Some((3, &mut 3)));
+    }
+}